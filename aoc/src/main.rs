@@ -0,0 +1,15 @@
+use std::process::ExitCode;
+
+use aocutil::Day;
+
+fn main() -> ExitCode {
+    let days = [
+        Day::of::<day1::Day1>(),
+        Day::of::<day2::Day2>(),
+        Day::of::<day3::Day3>(),
+        Day::of::<day4::Day4>(),
+        Day::of::<day20::Day20>(),
+    ];
+
+    aocutil::runner::run_workspace(&days)
+}
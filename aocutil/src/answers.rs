@@ -0,0 +1,189 @@
+//! Known-good answers of a day, stored in a small TOML file next to its input:
+//!
+//! ```toml
+//! part1 = "4361"
+//! part2 = "467835"
+//! ```
+
+use std::{fmt::Display, io, path::Path, str::FromStr};
+
+use crate::Part;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Answers {
+    /// Read the answers stored at `path`, which may not exist yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => content
+                .parse()
+                .map_err(|err| format!("{}: {err}", path.display())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(format!("{}: {err}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, answer: String) {
+        match part {
+            Part::One => self.part1 = Some(answer),
+            Part::Two => self.part2 = Some(answer),
+        }
+    }
+
+    pub fn check(&self, part: Part, actual: &str) -> Verdict {
+        match self.get(part) {
+            None => Verdict::New,
+            Some(expected) if expected == actual => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Self::default();
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let line_number = index + 1;
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {line_number}: expected `key = \"value\"`"))?;
+
+            let part = match key.trim() {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                key => return Err(format!("line {line_number}: unknown key `{key}`")),
+            };
+
+            let value = parse_string(value.trim())
+                .ok_or_else(|| format!("line {line_number}: expected a quoted string"))?;
+
+            answers.set(part, value);
+        }
+
+        Ok(answers)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(answer) = &self.part1 {
+            writeln!(f, "part1 = \"{}\"", escape(answer))?;
+        }
+
+        if let Some(answer) = &self.part2 {
+            writeln!(f, "part2 = \"{}\"", escape(answer))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn parse_string(s: &str) -> Option<String> {
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next().filter(|c| *c == '\\' || *c == '"')?),
+            '"' => return None,
+            c => value.push(c),
+        }
+    }
+
+    Some(value)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    New,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {expected})"),
+            Verdict::New => write!(f, "NEW"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers: Answers = "\
+# Day 3
+part1 = \"4361\"
+
+part2=\"a \\\"quoted\\\" \\\\ answer\"
+"
+        .parse()
+        .unwrap();
+
+        assert_eq!(Some("4361"), answers.get(Part::One));
+        assert_eq!(Some("a \"quoted\" \\ answer"), answers.get(Part::Two));
+
+        assert_eq!(answers, answers.to_string().parse().unwrap(), "round trip");
+
+        assert_eq!(Ok(Answers::default()), "".parse());
+        assert!("part3 = \"1\"".parse::<Answers>().is_err(), "unknown key");
+        assert!("part1 = 1".parse::<Answers>().is_err(), "unquoted value");
+        assert!(
+            "part1 = \"1\" \"".parse::<Answers>().is_err(),
+            "stray quote"
+        );
+        assert!("part1".parse::<Answers>().is_err(), "missing value");
+    }
+
+    #[test]
+    fn test_check_answers() {
+        let mut answers = Answers::default();
+        answers.set(Part::One, String::from("42"));
+
+        assert_eq!(Verdict::Pass, answers.check(Part::One, "42"));
+        assert_eq!(
+            Verdict::Fail {
+                expected: String::from("42")
+            },
+            answers.check(Part::One, "43")
+        );
+        assert_eq!(Verdict::New, answers.check(Part::Two, "42"));
+    }
+}
@@ -0,0 +1,150 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+/// How long to keep measuring a function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Budget {
+    Runs(usize),
+    Time(Duration),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchOptions {
+    pub warmup_runs: usize,
+    pub budget: Budget,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup_runs: 3,
+            budget: Budget::Time(Duration::from_secs(1)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchStats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl BenchStats {
+    /// Compute the statistics of a non-empty set of measurements.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples to compute stats from");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let runs = sorted.len();
+        let middle = runs / 2;
+
+        let median = if runs.is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        // Nearest-rank percentile.
+        let p95_rank = (runs * 95).div_ceil(100);
+
+        let mean_secs = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / runs as f64;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean_secs).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+
+        Self {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean_secs),
+            p95: sorted[p95_rank - 1],
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl Display for BenchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:?}, median {:?}, mean {:?}, p95 {:?}, stddev {:?} over {} runs",
+            self.min, self.median, self.mean, self.p95, self.stddev, self.runs
+        )
+    }
+}
+
+/// Measure `f` repeatedly after a few warm-up runs, and return its last result.
+pub fn bench<R>(input: &str, f: fn(&str) -> R, options: &BenchOptions) -> (R, BenchStats) {
+    for _ in 0..options.warmup_runs {
+        f(input);
+    }
+
+    let mut samples = Vec::new();
+    let started = Instant::now();
+
+    loop {
+        let timer = Instant::now();
+        let res = f(input);
+        samples.push(timer.elapsed());
+
+        let done = match options.budget {
+            Budget::Runs(runs) => samples.len() >= runs,
+            Budget::Time(time) => started.elapsed() >= time,
+        };
+
+        if done {
+            return (res, BenchStats::from_samples(&samples));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = BenchStats::from_samples(&millis(&[4, 2, 8, 6]));
+
+        assert_eq!(4, stats.runs);
+        assert_eq!(Duration::from_millis(2), stats.min);
+        assert_eq!(Duration::from_millis(5), stats.median, "even count");
+        assert_eq!(Duration::from_millis(5), stats.mean);
+        assert_eq!(Duration::from_millis(8), stats.p95);
+        assert!((stats.stddev.as_secs_f64() - 5f64.sqrt() / 1000.0).abs() < 1e-9);
+
+        let stats = BenchStats::from_samples(&millis(&[3, 1, 2]));
+        assert_eq!(Duration::from_millis(2), stats.median, "odd count");
+
+        let samples = millis(&(1..=100).collect::<Vec<_>>());
+        let stats = BenchStats::from_samples(&samples);
+        assert_eq!(Duration::from_millis(95), stats.p95);
+    }
+
+    #[test]
+    fn test_bench_runs_budget() {
+        let options = BenchOptions {
+            warmup_runs: 2,
+            budget: Budget::Runs(5),
+        };
+
+        let (res, stats) = bench("abc", str::len, &options);
+
+        assert_eq!(3, res);
+        assert_eq!(5, stats.runs);
+    }
+}
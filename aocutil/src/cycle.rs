@@ -0,0 +1,259 @@
+//! Cycle detection in sequences of states, to skip ahead in simulations which run for billions of
+//! steps but repeat themselves much sooner.
+//!
+//! Step `n` is the state reached by applying `step` `n` times to `start`, so step 0 is `start`.
+
+use std::{collections::HashMap, hash::Hash, iter};
+
+/// The shape of a sequence of states which repeats: after `mu` steps, it loops every `lambda`
+/// steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step of the loop.
+    pub mu: usize,
+    /// The length of the loop.
+    pub lambda: usize,
+}
+
+impl Cycle {
+    /// The first step with the same state as step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.mu {
+            n
+        } else {
+            self.mu + (n - self.mu) % self.lambda
+        }
+    }
+}
+
+/// Floyd's tortoise and hare: find the cycle with constant memory.
+///
+/// Never returns if the sequence does not repeat.
+pub fn floyd<S: Clone + PartialEq>(start: &S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(start);
+    let mut hare = step(&tortoise);
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut mu = 0;
+    tortoise = start.clone();
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    hare = step(&tortoise);
+
+    while tortoise != hare {
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    Cycle { mu, lambda }
+}
+
+/// Brent's algorithm: find the cycle with constant memory, in fewer steps than [`floyd`].
+///
+/// Never returns if the sequence does not repeat.
+pub fn brent<S: Clone + PartialEq>(start: &S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(start);
+
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+
+    for _ in 0..lambda {
+        hare = step(&hare);
+    }
+
+    let mut mu = 0;
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    Cycle { mu, lambda }
+}
+
+/// The states of a sequence, up to the first repeated one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct History<S> {
+    states: Vec<S>,
+    cycle: Option<Cycle>,
+}
+
+impl<S> History<S> {
+    /// The cycle of the sequence, unless it was not found within the allowed steps.
+    pub fn cycle(&self) -> Option<Cycle> {
+        self.cycle
+    }
+
+    /// The distinct states met, from step 0.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// The first step with the same state as step `n`, if it is known.
+    pub fn equivalent_step(&self, n: usize) -> Option<usize> {
+        match self.cycle {
+            Some(cycle) => Some(cycle.equivalent_step(n)),
+            None => (n < self.states.len()).then_some(n),
+        }
+    }
+
+    /// The state at step `n`, if it is known.
+    pub fn state_at(&self, n: usize) -> Option<&S> {
+        self.states.get(self.equivalent_step(n)?)
+    }
+}
+
+/// Apply `step` from `start` until a state repeats, or until `max_steps` steps, remembering every
+/// state met.
+pub fn record<S: Clone + Hash + Eq>(
+    start: S,
+    mut step: impl FnMut(&S) -> S,
+    max_steps: usize,
+) -> History<S> {
+    record_iter(
+        iter::successors(Some(start), |state| Some(step(state))),
+        max_steps,
+    )
+}
+
+/// Like [`record`], for a sequence which moves itself forward: `states` yields step 0, then step 1,
+/// and so on. It is only advanced until a state repeats, or until `max_steps` steps, or until it
+/// ends.
+pub fn record_iter<S: Clone + Hash + Eq>(
+    states: impl IntoIterator<Item = S>,
+    max_steps: usize,
+) -> History<S> {
+    let mut seen = HashMap::new();
+    let mut recorded = Vec::new();
+    let mut cycle = None;
+
+    for state in states {
+        if let Some(&mu) = seen.get(&state) {
+            cycle = Some(Cycle {
+                mu,
+                lambda: recorded.len() - mu,
+            });
+            break;
+        }
+
+        seen.insert(state.clone(), recorded.len());
+        recorded.push(state);
+
+        if recorded.len() > max_steps {
+            break;
+        }
+    }
+
+    History {
+        states: recorded,
+        cycle,
+    }
+}
+
+/// The state at step `n`, skipping the repetitions of the sequence.
+pub fn nth_state<S: Clone + Hash + Eq>(start: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    record(start, step, n)
+        .state_at(n)
+        .expect("record runs until step n or a cycle")
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, then 3, 4, 5, 6, 7 forever.
+    fn rho(n: &u32) -> u32 {
+        if *n == 7 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let expected = Cycle { mu: 3, lambda: 5 };
+
+        assert_eq!(expected, floyd(&0, rho));
+        assert_eq!(expected, brent(&0, rho));
+        assert_eq!(Some(expected), record(0, rho, usize::MAX).cycle());
+
+        let pure_loop = Cycle { mu: 0, lambda: 5 };
+        assert_eq!(pure_loop, floyd(&3, rho));
+        assert_eq!(pure_loop, brent(&3, rho));
+        assert_eq!(Some(pure_loop), record(3, rho, usize::MAX).cycle());
+
+        let fixed_point = Cycle { mu: 0, lambda: 1 };
+        assert_eq!(fixed_point, brent(&1, |n| *n));
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        let cycle = Cycle { mu: 3, lambda: 5 };
+        assert_eq!(2, cycle.equivalent_step(2));
+        assert_eq!(3, cycle.equivalent_step(8));
+        assert_eq!(4, cycle.equivalent_step(1_000_000_004));
+
+        let history = record(0, rho, usize::MAX);
+        assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7], history.states());
+        assert_eq!(Some(&4), history.state_at(1_000_000_004));
+
+        assert_eq!(4, nth_state(0, rho, 1_000_000_004));
+        assert_eq!(2, nth_state(0, rho, 2));
+    }
+
+    #[test]
+    fn test_record_within_limit() {
+        let history = record(0, rho, 4);
+
+        assert_eq!(None, history.cycle());
+        assert_eq!(&[0, 1, 2, 3, 4], history.states());
+        assert_eq!(Some(&4), history.state_at(4));
+        assert_eq!(None, history.state_at(5));
+    }
+
+    #[test]
+    fn test_record_iter() {
+        let mut steps = 0;
+        let history = record_iter(
+            [0, 1, 2, 3, 4, 5, 6, 7, 3, 4]
+                .into_iter()
+                .inspect(|_| steps += 1),
+            usize::MAX,
+        );
+
+        assert_eq!(Some(Cycle { mu: 3, lambda: 5 }), history.cycle());
+        assert_eq!(9, steps, "stops at the first repeated state");
+
+        let history = record_iter([0, 1, 2], usize::MAX);
+        assert_eq!(None, history.cycle());
+        assert_eq!(&[0, 1, 2], history.states());
+    }
+}
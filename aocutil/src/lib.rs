@@ -1,3 +1,14 @@
+pub mod answers;
+pub mod bench;
+pub mod cycle;
+pub mod math;
+pub mod ranges;
+pub mod runner;
+pub mod scaffold;
+mod solution;
+
+pub use solution::{Day, Part, Solution};
+
 use std::time::{Duration, Instant};
 
 pub fn timed<R>(input: &str, f: fn(&str) -> R) -> (R, Duration) {
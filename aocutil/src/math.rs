@@ -0,0 +1,405 @@
+//! Number theory helpers, generic over the primitive integer types.
+//!
+//! Everything that could overflow returns `None` instead, as do the operations without a result,
+//! such as the inverse of a number which is not coprime with the modulus.
+
+use std::{
+    fmt::Debug,
+    ops::{Div, Rem},
+};
+
+/// A primitive integer type.
+pub trait Integer: Copy + Ord + Debug + Div<Output = Self> + Rem<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    fn checked_rem(self, other: Self) -> Option<Self>;
+
+    /// The absolute value, unless it does not fit.
+    fn checked_abs(self) -> Option<Self>;
+
+    /// The integer square root, unless `self` is negative.
+    fn checked_isqrt(self) -> Option<Self>;
+
+    fn to_i128(self) -> Option<i128>;
+
+    fn from_i128(value: i128) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),* ; abs: $abs:expr, isqrt: $isqrt:expr) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_rem(self, other: Self) -> Option<Self> {
+                    <$t>::checked_rem(self, other)
+                }
+
+                fn checked_abs(self) -> Option<Self> {
+                    $abs(self)
+                }
+
+                fn checked_isqrt(self) -> Option<Self> {
+                    $isqrt(self)
+                }
+
+                fn to_i128(self) -> Option<i128> {
+                    i128::try_from(self).ok()
+                }
+
+                fn from_i128(value: i128) -> Option<Self> {
+                    Self::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(
+    i8, i16, i32, i64, i128, isize;
+    abs: |n: Self| n.checked_abs(),
+    isqrt: |n: Self| n.checked_isqrt()
+);
+impl_integer!(
+    u8, u16, u32, u64, u128, usize;
+    abs: Some,
+    isqrt: |n: Self| Some(n.isqrt())
+);
+
+/// The greatest common divisor, which is never negative, unless it does not fit: this only
+/// happens for the minimum of a signed type and 0 or itself. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a, b);
+
+    while b != T::ZERO {
+        // Only the minimum of a signed type divided by -1 overflows, and it leaves no remainder.
+        (a, b) = (b, a.checked_rem(b).unwrap_or(T::ZERO));
+    }
+
+    a.checked_abs()
+}
+
+/// The least common multiple, which is never negative. It is 0 if `a` or `b` is.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    (a / gcd(a, b)?).checked_mul(b)?.checked_abs()
+}
+
+/// The least common multiple of all `values`, or 1 if there is none.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::ONE, |multiple, value| lcm(multiple, value))
+}
+
+/// Find `(g, x, y)` such that `g = gcd(a, b) = a * x + b * y`, with `g` never negative, unless
+/// one of them does not fit in `T`, as negative coefficients for an unsigned type.
+pub fn extended_gcd<T: Integer>(a: T, b: T) -> Option<(T, T, T)> {
+    let (g, x, y) = bezout(a.to_i128()?, b.to_i128()?)?;
+
+    Some((T::from_i128(g)?, T::from_i128(x)?, T::from_i128(y)?))
+}
+
+/// The extended Euclidean algorithm, unless it overflows.
+fn bezout(a: i128, b: i128) -> Option<(i128, i128, i128)> {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1_i128, 0_i128);
+    let (mut old_y, mut y) = (0_i128, 1_i128);
+
+    while r != 0 {
+        let quotient = old_r.checked_div(r)?;
+
+        (old_r, r) = (r, old_r.checked_sub(quotient.checked_mul(r)?)?);
+        (old_x, x) = (x, old_x.checked_sub(quotient.checked_mul(x)?)?);
+        (old_y, y) = (y, old_y.checked_sub(quotient.checked_mul(y)?)?);
+    }
+
+    if old_r < 0 {
+        Some((
+            old_r.checked_neg()?,
+            old_x.checked_neg()?,
+            old_y.checked_neg()?,
+        ))
+    } else {
+        Some((old_r, old_x, old_y))
+    }
+}
+
+/// The `x` in `0..modulus` such that `a * x ≡ 1 (mod modulus)`, if `a` and `modulus` are coprime
+/// and `modulus` is positive.
+pub fn mod_inv<T: Integer>(a: T, modulus: T) -> Option<T> {
+    let modulus = modulus.to_i128().filter(|modulus| *modulus > 0)?;
+
+    T::from_i128(inverse(a.to_i128()?, modulus)?)
+}
+
+fn inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (g, x, _y) = bezout(a.rem_euclid(modulus), modulus)?;
+
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// `base` to the power `exp`, modulo `modulus`, in `0..modulus`. `exp` must not be negative,
+/// and `modulus` must be positive.
+pub fn mod_pow<T: Integer>(base: T, exp: T, modulus: T) -> Option<T> {
+    let modulus = modulus.to_i128().filter(|modulus| *modulus > 0)?;
+    let mut exp = exp.to_i128().filter(|exp| *exp >= 0)?;
+    let mut base = base.to_i128()?.rem_euclid(modulus) as u128;
+    let mut result = (1 % modulus) as u128;
+
+    while exp > 0 {
+        if exp % 2 == 1 {
+            result = mul_mod(result, base, modulus as u128);
+        }
+
+        base = mul_mod(base, base, modulus as u128);
+        exp /= 2;
+    }
+
+    T::from_i128(result as i128)
+}
+
+/// `a * b % modulus` for `a` and `b` in `0..modulus`, doubling and adding when the product does
+/// not fit.
+fn mul_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+
+    let (mut a, mut b, mut result) = (a, b, 0);
+
+    while b > 0 {
+        if b % 2 == 1 {
+            result = add_mod(result, a, modulus);
+        }
+
+        a = add_mod(a, a, modulus);
+        b /= 2;
+    }
+
+    result
+}
+
+/// `(a + b) % modulus` for `a` and `b` in `0..modulus`, even when `a + b` does not fit.
+fn add_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+/// Solve the system of congruences `x ≡ residue (mod modulus)`, given as `(residue, modulus)`
+/// pairs: find the `(x, m)` such that the solutions are the numbers congruent to `x` modulo `m`,
+/// with `x` in `0..m`.
+///
+/// The moduli need not be coprime, but must be positive. There is no solution if the congruences
+/// contradict each other.
+pub fn crt<T: Integer>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let (x, m) =
+        congruences
+            .into_iter()
+            .try_fold((0_i128, 1_i128), |(x, m), (residue, modulus)| {
+                let modulus = modulus.to_i128().filter(|modulus| *modulus > 0)?;
+                let residue = residue.to_i128()?.rem_euclid(modulus);
+
+                combine(x, m, residue, modulus)
+            })?;
+
+    Some((T::from_i128(x)?, T::from_i128(m)?))
+}
+
+/// Merge `x ≡ r1 (mod m1)` and `x ≡ r2 (mod m2)` into a single congruence.
+fn combine(r1: i128, m1: i128, r2: i128, m2: i128) -> Option<(i128, i128)> {
+    let g = gcd(m1, m2)?;
+    let difference = r2 - r1;
+
+    if difference % g != 0 {
+        return None;
+    }
+
+    // r1 + m1 * k ≡ r2 (mod m2), so k ≡ (difference / g) * (m1 / g)⁻¹ (mod m2 / g).
+    let reduced = m2 / g;
+    let k = (difference / g)
+        .rem_euclid(reduced)
+        .checked_mul(inverse(m1 / g, reduced)?)?
+        % reduced;
+
+    let m = (m1 / g).checked_mul(m2)?;
+    let x = m1.checked_mul(k)?.checked_add(r1)?.rem_euclid(m);
+
+    Some((x, m))
+}
+
+/// The largest integer whose square is at most `n`, unless `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> Option<T> {
+    n.checked_isqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(Some(6), gcd(12, 18));
+        assert_eq!(Some(6), gcd(-12, 18));
+        assert_eq!(Some(5), gcd(0, -5));
+        assert_eq!(Some(0), gcd(0_u8, 0));
+        assert_eq!(Some(1), gcd(i64::MIN, -1), "the remainder overflows");
+        assert_eq!(Some(2), gcd(i64::MIN, 6));
+        assert_eq!(None, gcd(i64::MIN, 0), "2^63 does not fit");
+        assert_eq!(None, gcd(i64::MIN, i64::MIN));
+
+        assert_eq!(Some(36), lcm(12, 18));
+        assert_eq!(Some(36), lcm(-12, 18));
+        assert_eq!(Some(0), lcm(0, 7));
+        assert_eq!(None, lcm(200_u8, 3), "overflow");
+
+        assert_eq!(Some(60), lcm_all([3_u64, 4, 5, 6]));
+        assert_eq!(Some(1), lcm_all(Vec::<u32>::new()));
+        assert_eq!(None, lcm_all([u64::MAX, 2]));
+    }
+
+    #[test]
+    fn test_modular_arithmetic() {
+        assert_eq!(Some((1, -1, 1)), extended_gcd(2, 3));
+        assert_eq!(Some((6_i16, 1, -1)), extended_gcd(-12, -18));
+        assert_eq!(Some((4_u32, 1, 0)), extended_gcd(4, 0));
+        assert_eq!(None, extended_gcd(2_u32, 3), "negative coefficient");
+
+        assert_eq!(Some(4), mod_inv(3, 11));
+        assert_eq!(Some(7), mod_inv(-3, 11));
+        assert_eq!(None, mod_inv(4, 8), "not coprime");
+        assert_eq!(None, mod_inv(3, 0));
+
+        assert_eq!(Some(445), mod_pow(4, 13, 497));
+        assert_eq!(Some(0), mod_pow(2, 0, 1));
+        assert_eq!(Some(1), mod_pow(-1_i64, 2, 7));
+        assert_eq!(None, mod_pow(2, -1, 7));
+        assert_eq!(
+            Some(1),
+            mod_pow(3_u64, (1 << 61) - 2, (1 << 61) - 1),
+            "Fermat's little theorem"
+        );
+        assert_eq!(
+            Some(1),
+            mod_pow(u64::MAX - 1, 2, u64::MAX),
+            "the square overflows"
+        );
+        assert_eq!(
+            Some(i128::MAX - 8),
+            mod_pow(i128::MAX - 2, 3, i128::MAX),
+            "(-2)^3 modulo i128::MAX"
+        );
+        assert_eq!(None, mod_pow(3_u128, 5, u128::MAX), "modulus too large");
+
+        assert_eq!(Some((23, 105)), crt([(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((10, 12)), crt([(4, 6), (2, 4)]), "non-coprime moduli");
+        assert_eq!(None, crt([(1, 6), (2, 4)]), "contradiction");
+        assert_eq!(Some((0_u8, 1)), crt([]));
+        assert_eq!(None, crt([(1_u8, 251), (1, 241)]), "overflow of u8");
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(Some(0), isqrt(0));
+        assert_eq!(Some(3), isqrt(15));
+        assert_eq!(Some(4), isqrt(16));
+        assert_eq!(None, isqrt(-1));
+        assert_eq!(Some(u32::MAX as u64), isqrt(u64::MAX));
+    }
+
+    proptest! {
+        #[test]
+        fn gcd_divides_both(a in -10_000_i64..10_000, b in -10_000_i64..10_000) {
+            let g = gcd(a, b).unwrap();
+
+            prop_assert!(g >= 0);
+            if g != 0 {
+                prop_assert_eq!(0, a % g);
+                prop_assert_eq!(0, b % g);
+            }
+        }
+
+        #[test]
+        fn gcd_times_lcm(a in 1_u64..1_000_000, b in 1_u64..1_000_000) {
+            prop_assert_eq!(Some(a * b), lcm(a, b).map(|m| m * gcd(a, b).unwrap()));
+        }
+
+        #[test]
+        fn inverse_times_value(a in -1_000_i64..1_000, modulus in 2_i64..1_000) {
+            match mod_inv(a, modulus) {
+                Some(inverse) => prop_assert_eq!(1, (a * inverse).rem_euclid(modulus)),
+                None => prop_assert_ne!(Some(1), gcd(a, modulus)),
+            }
+        }
+
+        #[test]
+        fn bezout_identity(a in -10_000_i64..10_000, b in -10_000_i64..10_000) {
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+
+            prop_assert_eq!(gcd(a, b), Some(g));
+            prop_assert_eq!(g, a * x + b * y);
+        }
+
+        #[test]
+        fn mod_pow_is_repeated_product(base in -100_i64..100, exp in 0_i64..50, modulus in 1_i64..1_000) {
+            let expected = (0..exp).fold(1 % modulus, |product, _| (product * base).rem_euclid(modulus));
+
+            prop_assert_eq!(Some(expected), mod_pow(base, exp, modulus));
+        }
+
+        #[test]
+        fn mod_pow_near_max(base in u64::MAX - 1_000.., exp in 0_u64..50, modulus in u64::MAX - 1_000..) {
+            let expected = (0..exp).fold(1 % modulus as u128, |product, _| {
+                product * base as u128 % modulus as u128
+            });
+
+            prop_assert_eq!(Some(expected as u64), mod_pow(base, exp, modulus));
+        }
+
+        #[test]
+        fn crt_solves_every_congruence(congruences in prop::collection::vec((0_i64..100, 1_i64..12), 0..5)) {
+            match crt(congruences.clone()) {
+                Some((x, m)) => {
+                    prop_assert!((0..m).contains(&x));
+
+                    for (residue, modulus) in congruences {
+                        prop_assert_eq!(residue.rem_euclid(modulus), x % modulus);
+                        prop_assert_eq!(0, m % modulus);
+                    }
+                }
+                // Without a solution, none of the first lcm numbers is one.
+                None => {
+                    let m = lcm_all(congruences.iter().map(|(_residue, modulus)| *modulus)).unwrap();
+
+                    prop_assert!((0..m).all(|x| congruences
+                        .iter()
+                        .any(|(residue, modulus)| x % modulus != residue.rem_euclid(*modulus))));
+                }
+            }
+        }
+
+        #[test]
+        fn isqrt_is_floor_of_square_root(n in 0_u64..u64::MAX) {
+            let root = isqrt(n).unwrap() as u128;
+
+            prop_assert!(root * root <= n as u128);
+            prop_assert!((root + 1) * (root + 1) > n as u128);
+        }
+    }
+}
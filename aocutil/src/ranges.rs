@@ -0,0 +1,326 @@
+//! Sets of integers stored as ranges, for puzzles about intervals too large to enumerate.
+//!
+//! Every range is half-open, like [`Range`]: `3..5` holds 3 and 4, and `5..5` is empty.
+
+use std::{
+    iter::Sum,
+    ops::{Add, Range, Sub},
+};
+
+/// A set of values, stored as sorted, disjoint and non-adjacent ranges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a set from ranges which may be empty, unsorted, overlapping or adjacent.
+    fn coalesce(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|range| !range.is_empty());
+        ranges.sort_by_key(|range| range.start);
+
+        let mut coalesced: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+
+        for range in ranges {
+            match coalesced.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => coalesced.push(range),
+            }
+        }
+
+        Self { ranges: coalesced }
+    }
+
+    /// The ranges of the set, in increasing order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= value);
+
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.contains(&value))
+    }
+
+    /// Add the values of `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+
+        *self = Self::coalesce(ranges);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::coalesce(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut mine, mut theirs) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(a), Some(b)) = (mine.peek(), theirs.peek()) {
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+
+            if a.end <= b.end {
+                mine.next();
+            } else {
+                theirs.next();
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// The values of `self` which are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            for removed in other
+                .ranges
+                .iter()
+                .filter(|removed| removed.end > range.start && removed.start < range.end)
+            {
+                if removed.start > start {
+                    ranges.push(start..removed.start);
+                }
+
+                start = start.max(removed.end);
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// The number of values in the set.
+    pub fn size(&self) -> T
+    where
+        T: Sub<Output = T> + Sum,
+    {
+        self.ranges
+            .iter()
+            .map(|range| range.end - range.start)
+            .sum()
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::coalesce(vec![range])
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::coalesce(iter.into_iter().collect())
+    }
+}
+
+/// A piecewise translation of values: the values of each source range are moved to the range of
+/// the same length starting at its destination, and the others are left as they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffsetTable<T> {
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for OffsetTable<T> {
+    fn default() -> Self {
+        Self { pieces: Vec::new() }
+    }
+}
+
+impl<T> OffsetTable<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move the values of `source` to the range starting at `destination`.
+    ///
+    /// Panics if `source` overlaps the source of another piece.
+    pub fn insert(&mut self, source: Range<T>, destination: T) {
+        assert!(
+            self.pieces
+                .iter()
+                .all(|(other, _)| other.end <= source.start || source.end <= other.start),
+            "overlapping source ranges"
+        );
+
+        self.pieces.push((source, destination));
+    }
+
+    pub fn map(&self, value: T) -> T {
+        self.pieces
+            .iter()
+            .find(|(source, _destination)| source.contains(&value))
+            .map_or(value, |(source, destination)| {
+                *destination + (value - source.start)
+            })
+    }
+
+    /// Map every value of `set`, splitting its ranges where the pieces of the table start and
+    /// end.
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        let sources: RangeSet<T> = self
+            .pieces
+            .iter()
+            .map(|(source, _)| source.clone())
+            .collect();
+
+        let moved = self.pieces.iter().flat_map(|(source, destination)| {
+            set.intersection(&RangeSet::from(source.clone()))
+                .ranges
+                .into_iter()
+                .map(|range| {
+                    *destination + (range.start - source.start)
+                        ..*destination + (range.end - source.start)
+                })
+        });
+
+        moved.chain(set.difference(&sources).ranges).collect()
+    }
+}
+
+impl<T> FromIterator<(Range<T>, T)> for OffsetTable<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(iter: I) -> Self {
+        let mut table = Self::new();
+
+        for (source, destination) in iter {
+            table.insert(source, destination);
+        }
+
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_coalesce() {
+        let set: RangeSet<u64> = [5..8, 1..3, 3..4, 7..10, 12..12].into_iter().collect();
+
+        assert_eq!(&[1..4, 5..10], set.ranges(), "adjacent ranges merge");
+        assert_eq!(8, set.size());
+        assert!(set.contains(1));
+        assert!(set.contains(3));
+        assert!(!set.contains(4), "end is excluded");
+        assert!(set.contains(9));
+        assert!(!set.contains(10));
+
+        let mut set = set;
+        set.insert(4..5);
+        assert_eq!(RangeSet::from(1..10), set);
+        assert!(RangeSet::from(3..3).is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: RangeSet<i64> = [-5..0, 3..10].into_iter().collect();
+        let b: RangeSet<i64> = [-2..4, 9..12].into_iter().collect();
+
+        assert_eq!(RangeSet::from(-5..12), a.union(&b));
+        assert_eq!(&[-2..0, 3..4, 9..10], a.intersection(&b).ranges());
+        assert_eq!(&[-5..-2, 4..9], a.difference(&b).ranges());
+        assert_eq!(&[0..3, 10..12], b.difference(&a).ranges());
+
+        assert!(a.intersection(&RangeSet::new()).is_empty());
+        assert_eq!(a, a.difference(&RangeSet::from(0..3)), "touching only");
+    }
+
+    #[test]
+    fn test_offset_table() {
+        // The seed-to-soil map of 2023 day 5.
+        let table: OffsetTable<u64> = [(98..100, 50), (50..98, 52)].into_iter().collect();
+
+        assert_eq!(81, table.map(79));
+        assert_eq!(14, table.map(14));
+        assert_eq!(50, table.map(98));
+        assert_eq!(100, table.map(100));
+
+        let seeds: RangeSet<u64> = [79..93, 55..68].into_iter().collect();
+        assert_eq!(&[57..70, 81..95], table.map_set(&seeds).ranges());
+
+        // 45..50 and 100..101 stay, 98..100 moves to 50..52, and 50..98 to 52..100.
+        let across = RangeSet::from(45..101);
+        assert_eq!(RangeSet::from(45..101), table.map_set(&across));
+
+        let edge = RangeSet::from(97..99);
+        assert_eq!(&[50..51, 99..100], table.map_set(&edge).ranges());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_overlapping_offsets() {
+        let _table: OffsetTable<u64> = [(0..10, 20), (9..12, 40)].into_iter().collect();
+    }
+
+    fn values(set: &RangeSet<i32>) -> BTreeSet<i32> {
+        set.ranges().iter().cloned().flatten().collect()
+    }
+
+    fn range_set() -> impl Strategy<Value = RangeSet<i32>> {
+        prop::collection::vec((-20_i32..20, 0_i32..8), 0..5).prop_map(|ranges| {
+            ranges
+                .into_iter()
+                .map(|(start, len)| start..start + len)
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn set_operations_match_sets_of_values(a in range_set(), b in range_set()) {
+            let (a_values, b_values) = (values(&a), values(&b));
+
+            prop_assert_eq!(&a_values | &b_values, values(&a.union(&b)));
+            prop_assert_eq!(&a_values & &b_values, values(&a.intersection(&b)));
+            prop_assert_eq!(&a_values - &b_values, values(&a.difference(&b)));
+            prop_assert_eq!(a_values.len() as i32, a.size());
+
+            for set in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+                prop_assert!(set.ranges().windows(2).all(|pair| pair[0].end < pair[1].start));
+                prop_assert!(set.ranges().iter().all(|range| !range.is_empty()));
+            }
+        }
+    }
+}
@@ -0,0 +1,451 @@
+//! Entry points of the binaries: the one of each day, and the `aoc` one that can run any day.
+
+use std::{fmt::Display, path::Path, process::ExitCode, str::FromStr, time::Duration};
+
+use crate::{
+    answers::{Answers, Verdict},
+    bench::{bench, BenchOptions, Budget},
+    scaffold::{self, Dependency},
+    timed, Day, Part, Solution,
+};
+
+const USAGE: &str = "\
+Usage: aoc run [options] <day>...
+       aoc run [options] --all
+       aoc verify <day>...
+       aoc verify --all
+       aoc record <day> <part>
+       aoc new <day> [--chargrid] [--nom]";
+
+const DAY_USAGE: &str = "\
+Usage: dayX [options] [input]";
+
+const OPTIONS_USAGE: &str = "\
+Options:
+  --bench          Measure each part repeatedly and report statistics
+  --runs <n>       Benchmark with n runs instead of a time budget
+  --time <secs>    Benchmark each part for that long (default: 1)
+  --warmup <n>     Runs to do before benchmarking (default: 3)";
+
+/// Run both parts of `S` against the input file given as argument, or stdin.
+pub fn run_day<S: Solution>() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let (options, positional) = match parse_options(&args) {
+        Ok((options, positional)) if positional.len() <= 1 => (options, positional),
+        Ok(_) => exit_with_usage("Too many arguments", DAY_USAGE),
+        Err(message) => exit_with_usage(&message, DAY_USAGE),
+    };
+
+    let input_path = positional
+        .into_iter()
+        .next()
+        .unwrap_or(String::from("/dev/stdin"));
+
+    let input = std::fs::read_to_string(input_path).expect("Could not read input");
+
+    run_parts(&input, S::part1, S::part2, &options);
+}
+
+/// Run the `aoc` command line against the registered `days`.
+pub fn run_workspace(days: &[Day]) -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match parse_command(&args, days) {
+        Ok(Command::Run(selected, options)) => run_days(&selected, &options),
+        Ok(Command::Verify(selected)) => verify_days(&selected),
+        Ok(Command::Record(day, part)) => record_answer(&day, part),
+        Ok(Command::New(number, dependencies)) => new_day(number, &dependencies),
+        Err(message) => exit_with_usage(&message, USAGE),
+    }
+}
+
+fn exit_with_usage(message: &str, usage: &str) -> ! {
+    eprintln!("{message}\n\n{usage}\n\n{OPTIONS_USAGE}");
+    std::process::exit(2)
+}
+
+#[derive(Debug)]
+enum Command {
+    Run(Vec<Day>, RunOptions),
+    Verify(Vec<Day>),
+    Record(Day, Part),
+    New(u8, Vec<Dependency>),
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct RunOptions {
+    bench: Option<BenchOptions>,
+}
+
+fn parse_command(args: &[String], days: &[Day]) -> Result<Command, String> {
+    match args.split_first() {
+        Some((command, args)) if command == "run" => {
+            let (options, positional) = parse_options(args)?;
+
+            Ok(Command::Run(select_days(&positional, days)?, options))
+        }
+        Some((command, args)) if command == "verify" => {
+            Ok(Command::Verify(select_days(args, days)?))
+        }
+        Some((command, args)) if command == "record" => match args {
+            [day, part] => Ok(Command::Record(find_day(day, days)?, part.parse()?)),
+            _ => Err(String::from("Expected a day and a part")),
+        },
+        Some((command, args)) if command == "new" => parse_new_day(args),
+        Some((command, _args)) => Err(format!("Unknown command: {command}")),
+        None => Err(String::from("Missing command")),
+    }
+}
+
+/// Split the options out of `args`, and return them with the remaining positional arguments.
+fn parse_options(args: &[String]) -> Result<(RunOptions, Vec<String>), String> {
+    let mut options = RunOptions::default();
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bench" => {
+                options.bench.get_or_insert_with(BenchOptions::default);
+            }
+            "--runs" => {
+                let runs = parse_value(arg, args.next())?;
+                options
+                    .bench
+                    .get_or_insert_with(BenchOptions::default)
+                    .budget = Budget::Runs(runs);
+            }
+            "--time" => {
+                let secs: f64 = parse_value(arg, args.next())?;
+                let time = Duration::try_from_secs_f64(secs)
+                    .map_err(|_| format!("Invalid value for {arg}: {secs}"))?;
+                options
+                    .bench
+                    .get_or_insert_with(BenchOptions::default)
+                    .budget = Budget::Time(time);
+            }
+            "--warmup" => {
+                let warmup_runs = parse_value(arg, args.next())?;
+                options
+                    .bench
+                    .get_or_insert_with(BenchOptions::default)
+                    .warmup_runs = warmup_runs;
+            }
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    Ok((options, positional))
+}
+
+fn parse_value<T: FromStr>(option: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("Missing value for {option}"))?;
+
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {option}: {value}"))
+}
+
+fn select_days(args: &[String], days: &[Day]) -> Result<Vec<Day>, String> {
+    if args.iter().any(|arg| arg == "--all") {
+        return Ok(days.to_vec());
+    }
+
+    if args.is_empty() {
+        return Err(String::from("Missing day"));
+    }
+
+    args.iter().map(|arg| find_day(arg, days)).collect()
+}
+
+fn parse_new_day(args: &[String]) -> Result<Command, String> {
+    let mut number = None;
+    let mut dependencies = Vec::new();
+
+    for arg in args {
+        match arg.as_str() {
+            "--chargrid" => dependencies.push(Dependency::Chargrid),
+            "--nom" => dependencies.push(Dependency::Nom),
+            _ if number.is_none() => {
+                number = Some(
+                    arg.parse()
+                        .ok()
+                        .filter(|number| (1..=25).contains(number))
+                        .ok_or_else(|| format!("Invalid day: {arg}"))?,
+                )
+            }
+            _ => return Err(format!("Unexpected argument: {arg}")),
+        }
+    }
+
+    let number = number.ok_or("Missing day")?;
+
+    Ok(Command::New(number, dependencies))
+}
+
+fn find_day(arg: &str, days: &[Day]) -> Result<Day, String> {
+    let number: u8 = arg.parse().map_err(|_| format!("Invalid day: {arg}"))?;
+
+    days.iter()
+        .find(|day| day.number == number)
+        .copied()
+        .ok_or_else(|| format!("Day {number} is not registered"))
+}
+
+fn read_input(day: &Day) -> Option<String> {
+    let input_path = day.input_path();
+
+    match std::fs::read_to_string(&input_path) {
+        Ok(input) => Some(input),
+        Err(err) => {
+            eprintln!(
+                "Day {}: could not read {}: {err}",
+                day.number,
+                input_path.display()
+            );
+            None
+        }
+    }
+}
+
+fn run_days(days: &[Day], options: &RunOptions) -> ExitCode {
+    let mut status = ExitCode::SUCCESS;
+
+    for day in days {
+        let Some(input) = read_input(day) else {
+            status = ExitCode::FAILURE;
+            continue;
+        };
+
+        println!("Day {}", day.number);
+        run_parts(&input, day.part1, day.part2, options);
+    }
+
+    status
+}
+
+fn verify_days(days: &[Day]) -> ExitCode {
+    let mut status = ExitCode::SUCCESS;
+    let mut has_new_answers = false;
+
+    for day in days {
+        let Some(input) = read_input(day) else {
+            status = ExitCode::FAILURE;
+            continue;
+        };
+
+        let answers = match Answers::load(&day.answers_path()) {
+            Ok(answers) => answers,
+            Err(message) => {
+                eprintln!("Day {}: could not read answers: {message}", day.number);
+                status = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        println!("Day {}", day.number);
+
+        for part in Part::ALL {
+            let answer = day.solve(part, &input);
+            let verdict = answers.check(part, &answer);
+
+            println!("{part}: {answer} {verdict}");
+
+            match verdict {
+                Verdict::Pass => {}
+                Verdict::Fail { .. } => status = ExitCode::FAILURE,
+                Verdict::New => has_new_answers = true,
+            }
+        }
+    }
+
+    if has_new_answers {
+        println!("\nRecord new answers once accepted with `aoc record <day> <part>`.");
+    }
+
+    status
+}
+
+fn record_answer(day: &Day, part: Part) -> ExitCode {
+    let Some(input) = read_input(day) else {
+        return ExitCode::FAILURE;
+    };
+
+    let answers_path = day.answers_path();
+
+    let mut answers = match Answers::load(&answers_path) {
+        Ok(answers) => answers,
+        Err(message) => {
+            eprintln!("Day {}: could not read answers: {message}", day.number);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let answer = day.solve(part, &input);
+    answers.set(part, answer.clone());
+
+    if let Err(err) = answers.save(&answers_path) {
+        eprintln!(
+            "Day {}: could not write {}: {err}",
+            day.number,
+            answers_path.display()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    println!("Day {}, {part}: recorded {answer}", day.number);
+
+    ExitCode::SUCCESS
+}
+
+fn run_parts<R1: Display, R2: Display>(
+    input: &str,
+    part1: fn(&str) -> R1,
+    part2: fn(&str) -> R2,
+    options: &RunOptions,
+) {
+    run_part("Part 1", input, part1, options);
+    run_part("Part 2", input, part2, options);
+}
+
+fn run_part<R: Display>(name: &str, input: &str, f: fn(&str) -> R, options: &RunOptions) {
+    match &options.bench {
+        None => {
+            let (res, timing) = timed(input, f);
+            println!("{name}: {res} ({timing:?})");
+        }
+        Some(bench_options) => {
+            let (res, stats) = bench(input, f, bench_options);
+            println!("{name}: {res} ({stats})");
+        }
+    }
+}
+
+fn new_day(number: u8, dependencies: &[Dependency]) -> ExitCode {
+    match scaffold::new_day(Path::new("."), number, dependencies) {
+        Ok(()) => {
+            println!("Created day{number}, run it with `cargo run -p day{number}`");
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("Could not create day{number}: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_day(number: u8) -> Day {
+        Day {
+            number,
+            part1: |_input| String::from("1"),
+            part2: |_input| String::from("2"),
+        }
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn selected_numbers(command: Command) -> Vec<u8> {
+        match command {
+            Command::Run(days, _options) => days.iter().map(|day| day.number).collect(),
+            Command::Verify(days) => days.iter().map(|day| day.number).collect(),
+            Command::Record(day, _part) => vec![day.number],
+            Command::New(number, _dependencies) => vec![number],
+        }
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let days = [fake_day(1), fake_day(3), fake_day(20)];
+
+        let command = parse_command(&args(&["run", "20", "1"]), &days).unwrap();
+        assert_eq!(vec![20, 1], selected_numbers(command));
+
+        let command = parse_command(&args(&["run", "--all"]), &days).unwrap();
+        assert_eq!(vec![1, 3, 20], selected_numbers(command));
+
+        let command = parse_command(&args(&["verify", "--all"]), &days).unwrap();
+        assert_eq!(vec![1, 3, 20], selected_numbers(command));
+
+        let command = parse_command(&args(&["record", "3", "2"]), &days).unwrap();
+        assert!(matches!(command, Command::Record(day, Part::Two) if day.number == 3));
+
+        let command = parse_command(&args(&["new", "--nom", "5", "--chargrid"]), &days).unwrap();
+        assert!(matches!(
+            command,
+            Command::New(5, dependencies)
+                if dependencies == vec![Dependency::Nom, Dependency::Chargrid]
+        ));
+
+        assert!(parse_command(&args(&[]), &days).is_err(), "no command");
+        assert!(
+            parse_command(&args(&["fly"]), &days).is_err(),
+            "unknown command"
+        );
+        assert!(parse_command(&args(&["run"]), &days).is_err(), "no day");
+        assert!(
+            parse_command(&args(&["run", "x"]), &days).is_err(),
+            "invalid day"
+        );
+        assert!(
+            parse_command(&args(&["run", "2"]), &days).is_err(),
+            "unregistered day"
+        );
+        assert!(
+            parse_command(&args(&["record", "3"]), &days).is_err(),
+            "missing part"
+        );
+        assert!(
+            parse_command(&args(&["record", "3", "4"]), &days).is_err(),
+            "invalid part"
+        );
+        assert!(
+            parse_command(&args(&["new", "26"]), &days).is_err(),
+            "invalid new day"
+        );
+        assert!(
+            parse_command(&args(&["new", "5", "6"]), &days).is_err(),
+            "several new days"
+        );
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let (options, positional) = parse_options(&args(&["input.txt"])).unwrap();
+        assert_eq!(RunOptions::default(), options);
+        assert_eq!(args(&["input.txt"]), positional);
+
+        let (options, positional) = parse_options(&args(&["--bench", "3"])).unwrap();
+        assert_eq!(Some(BenchOptions::default()), options.bench);
+        assert_eq!(args(&["3"]), positional);
+
+        let (options, _) = parse_options(&args(&["--runs", "10", "--warmup", "0"])).unwrap();
+        let expected = BenchOptions {
+            warmup_runs: 0,
+            budget: Budget::Runs(10),
+        };
+        assert_eq!(Some(expected), options.bench, "--runs implies --bench");
+
+        let (options, _) = parse_options(&args(&["--time", "0.5"])).unwrap();
+        assert_eq!(
+            Some(Budget::Time(Duration::from_millis(500))),
+            options.bench.map(|bench| bench.budget)
+        );
+
+        assert!(parse_options(&args(&["--runs"])).is_err(), "missing value");
+        assert!(
+            parse_options(&args(&["--runs", "x"])).is_err(),
+            "invalid value"
+        );
+        assert!(
+            parse_options(&args(&["--time", "-1"])).is_err(),
+            "negative time"
+        );
+    }
+}
@@ -0,0 +1,351 @@
+//! Creation of the crate of a new day in the workspace, as `aoc new` does.
+
+use std::{fs, path::Path};
+
+/// Optional dependencies of a new day, on top of aocutil.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dependency {
+    Chargrid,
+    Nom,
+}
+
+impl Dependency {
+    fn manifest_line(&self) -> &'static str {
+        match self {
+            Dependency::Chargrid => r#"chargrid = { version = "0.1.0", path = "../chargrid" }"#,
+            Dependency::Nom => r#"nom = "7""#,
+        }
+    }
+}
+
+/// Create the `day{number}` crate in the workspace at `root`, and register it in the workspace
+/// members and in the `aoc` binary.
+///
+/// Everything is checked before anything is written, and if a write fails the files already
+/// written are put back, so that a failure leaves the workspace untouched unless putting them
+/// back fails too.
+pub fn new_day(root: &Path, number: u8, dependencies: &[Dependency]) -> Result<(), String> {
+    let name = format!("day{number}");
+    let day_dir = root.join(&name);
+
+    if day_dir.exists() {
+        return Err(format!("{} already exists", day_dir.display()));
+    }
+
+    let workspace_manifest = read(root, "Cargo.toml")?;
+    let aoc_manifest = read(root, "aoc/Cargo.toml")?;
+    let aoc_main = read(root, "aoc/src/main.rs")?;
+
+    let files = [
+        (
+            "Cargo.toml",
+            insert_member(&workspace_manifest, &name)?,
+            Some(workspace_manifest.as_str()),
+        ),
+        (
+            "aoc/Cargo.toml",
+            register_dependency(&aoc_manifest, number),
+            Some(aoc_manifest.as_str()),
+        ),
+        (
+            "aoc/src/main.rs",
+            register_day(&aoc_main, number)?,
+            Some(aoc_main.as_str()),
+        ),
+    ];
+    let day_files = [
+        ("Cargo.toml", day_manifest(number, dependencies), None),
+        (
+            "src/lib.rs",
+            render(&read(root, "lib.rs.template")?, number),
+            None,
+        ),
+        (
+            "src/main.rs",
+            render(&read(root, "main.rs.template")?, number),
+            None,
+        ),
+    ];
+
+    fs::create_dir_all(day_dir.join("src")).map_err(|err| err.to_string())?;
+
+    let result = write_all(&day_dir, &day_files).and_then(|()| write_all(root, &files));
+
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&day_dir);
+    }
+
+    result
+}
+
+/// Write each `(path, content, original)` under `root`, or put back the files already written if
+/// one of the writes fails: their `original` content, or nothing for new files.
+fn write_all(root: &Path, files: &[(&str, String, Option<&str>)]) -> Result<(), String> {
+    for (written, (path, content, _original)) in files.iter().enumerate() {
+        if let Err(err) = write(root, path, content) {
+            for (path, _content, original) in &files[..written] {
+                let _ = match original {
+                    Some(original) => write(root, path, original),
+                    None => fs::remove_file(root.join(path)).map_err(|err| err.to_string()),
+                };
+            }
+
+            return Err(err);
+        }
+    }
+
+    Ok(())
+}
+
+fn read(root: &Path, path: &str) -> Result<String, String> {
+    fs::read_to_string(root.join(path)).map_err(|err| format!("could not read {path}: {err}"))
+}
+
+fn write(root: &Path, path: &str, content: &str) -> Result<(), String> {
+    fs::write(root.join(path), content).map_err(|err| format!("could not write {path}: {err}"))
+}
+
+fn render(template: &str, number: u8) -> String {
+    template.replace("{day}", &number.to_string())
+}
+
+fn day_manifest(number: u8, dependencies: &[Dependency]) -> String {
+    let mut manifest = format!(
+        r#"[package]
+name = "day{number}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aocutil = {{ version = "0.1.0", path = "../aocutil" }}
+"#
+    );
+
+    for dependency in [Dependency::Chargrid, Dependency::Nom] {
+        if dependencies.contains(&dependency) {
+            manifest.push_str(dependency.manifest_line());
+            manifest.push('\n');
+        }
+    }
+
+    manifest
+}
+
+/// Sort key putting `day4` before `day20`.
+fn member_key(name: &str) -> (&str, Option<u32>) {
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+
+    (prefix, name[prefix.len()..].parse().ok())
+}
+
+fn insert_member(manifest: &str, name: &str) -> Result<String, String> {
+    let start = manifest
+        .find("members = [")
+        .ok_or("no workspace members in Cargo.toml")?
+        + "members = [".len();
+    let end = start
+        + manifest[start..]
+            .find(']')
+            .ok_or("unterminated workspace members in Cargo.toml")?;
+
+    let mut members: Vec<&str> = manifest[start..end]
+        .split(',')
+        .map(|member| member.trim().trim_matches('"'))
+        .filter(|member| !member.is_empty())
+        .collect();
+
+    if members.contains(&name) {
+        return Err(format!("{name} is already a workspace member"));
+    }
+
+    members.push(name);
+    members.sort_by_key(|member| member_key(member));
+
+    let members = members
+        .iter()
+        .map(|member| format!("\"{member}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Ok(format!(
+        "{}{members}{}",
+        &manifest[..start],
+        &manifest[end..]
+    ))
+}
+
+/// Insert `line` among the lines for which `day_of` gives a day number, keeping them sorted.
+fn insert_day_line(
+    content: &str,
+    number: u8,
+    line: &str,
+    day_of: impl Fn(&str) -> Option<u8>,
+) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+
+    let day_lines: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| day_of(line).map(|day| (index, day)))
+        .collect();
+
+    let (last_index, _) = day_lines.last()?;
+
+    let position = day_lines
+        .iter()
+        .find(|(_, day)| *day > number)
+        .map(|(index, _)| *index)
+        .unwrap_or(last_index + 1);
+
+    let mut lines: Vec<String> = lines.into_iter().map(String::from).collect();
+    lines.insert(position, line.to_string());
+
+    Some(lines.join("\n") + "\n")
+}
+
+fn register_dependency(manifest: &str, number: u8) -> String {
+    let line = format!(r#"day{number} = {{ version = "0.1.0", path = "../day{number}" }}"#);
+
+    insert_day_line(manifest, number, &line, |line| {
+        line.strip_prefix("day")?.split_once(' ')?.0.parse().ok()
+    })
+    .unwrap_or_else(|| format!("{manifest}{line}\n"))
+}
+
+fn register_day(main_rs: &str, number: u8) -> Result<String, String> {
+    let line = format!("        Day::of::<day{number}::Day{number}>(),");
+
+    insert_day_line(main_rs, number, &line, |line| {
+        line.trim()
+            .strip_prefix("Day::of::<day")?
+            .split_once(':')?
+            .0
+            .parse()
+            .ok()
+    })
+    .ok_or_else(|| String::from("no registered day in aoc/src/main.rs"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_member() {
+        let manifest = "[workspace]\n\nmembers = [\"aoc\", \"day1\", \"day4\", \"day20\"]\n";
+
+        assert_eq!(
+            "[workspace]\n\nmembers = [\"aoc\", \"day1\", \"day4\", \"day5\", \"day20\"]\n",
+            insert_member(manifest, "day5").unwrap()
+        );
+
+        assert_eq!(
+            "[workspace]\n\nmembers = [\"aoc\", \"day1\", \"day4\", \"day20\", \"day21\"]\n",
+            insert_member(manifest, "day21").unwrap()
+        );
+
+        assert!(insert_member(manifest, "day4").is_err(), "already a member");
+        assert!(
+            insert_member("[workspace]\n", "day4").is_err(),
+            "no members"
+        );
+    }
+
+    #[test]
+    fn test_register_day() {
+        let main_rs = "\
+fn main() -> ExitCode {
+    let days = [
+        Day::of::<day1::Day1>(),
+        Day::of::<day20::Day20>(),
+    ];
+}
+";
+
+        assert_eq!(
+            "\
+fn main() -> ExitCode {
+    let days = [
+        Day::of::<day1::Day1>(),
+        Day::of::<day5::Day5>(),
+        Day::of::<day20::Day20>(),
+    ];
+}
+",
+            register_day(main_rs, 5).unwrap()
+        );
+
+        assert!(register_day("fn main() {}\n", 5).is_err());
+    }
+
+    #[test]
+    fn test_register_dependency() {
+        let manifest = "\
+[dependencies]
+aocutil = { version = \"0.1.0\", path = \"../aocutil\" }
+day1 = { version = \"0.1.0\", path = \"../day1\" }
+";
+
+        assert_eq!(
+            "\
+[dependencies]
+aocutil = { version = \"0.1.0\", path = \"../aocutil\" }
+day1 = { version = \"0.1.0\", path = \"../day1\" }
+day5 = { version = \"0.1.0\", path = \"../day5\" }
+",
+            register_dependency(manifest, 5)
+        );
+    }
+
+    #[test]
+    fn test_day_manifest() {
+        let manifest = day_manifest(5, &[Dependency::Nom, Dependency::Chargrid]);
+
+        assert!(manifest.contains("name = \"day5\""));
+        assert!(manifest.ends_with(
+            "\
+aocutil = { version = \"0.1.0\", path = \"../aocutil\" }
+chargrid = { version = \"0.1.0\", path = \"../chargrid\" }
+nom = \"7\"
+"
+        ));
+    }
+
+    #[test]
+    fn test_new_day_refuses_existing_day() {
+        let root = std::env::temp_dir().join(format!("aocutil-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("day3")).unwrap();
+
+        let result = new_day(&root, 3, &[]);
+
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_write_all_puts_files_back() {
+        let root = std::env::temp_dir().join(format!("aocutil-write-all-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("old.txt"), "old").unwrap();
+
+        let result = write_all(
+            &root,
+            &[
+                ("old.txt", String::from("new"), Some("old")),
+                ("new.txt", String::from("new"), None),
+                ("missing/new.txt", String::from("new"), None),
+            ],
+        );
+
+        let old = fs::read_to_string(root.join("old.txt")).unwrap();
+        let new_exists = root.join("new.txt").exists();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(result.is_err());
+        assert_eq!("old", old);
+        assert!(!new_exists);
+    }
+}
@@ -0,0 +1,113 @@
+use std::{fmt::Display, path::PathBuf, str::FromStr};
+
+/// The solution to both parts of a day's puzzle.
+pub trait Solution {
+    const DAY: u8;
+
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn part1(input: &str) -> Self::Answer1;
+    fn part2(input: &str) -> Self::Answer2;
+}
+
+/// A [`Solution`] with its answer types erased, so that days can be listed together.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    pub part1: fn(&str) -> String,
+    pub part2: fn(&str) -> String,
+}
+
+impl Day {
+    pub fn of<S: Solution>() -> Self {
+        Self {
+            number: S::DAY,
+            part1: |input| S::part1(input).to_string(),
+            part2: |input| S::part2(input).to_string(),
+        }
+    }
+
+    pub fn solve(&self, part: Part, input: &str) -> String {
+        match part {
+            Part::One => (self.part1)(input),
+            Part::Two => (self.part2)(input),
+        }
+    }
+
+    /// Where the puzzle input of this day lives, relative to the workspace root.
+    pub fn input_path(&self) -> PathBuf {
+        PathBuf::from(format!("day{}/input.txt", self.number))
+    }
+
+    /// Where the known-good answers of this day live, next to its input.
+    pub fn answers_path(&self) -> PathBuf {
+        PathBuf::from(format!("day{}/answers.toml", self.number))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "Part 1"),
+            Part::Two => write!(f, "Part 2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "1" => Part::One,
+            "2" => Part::Two,
+            _ => return Err(format!("Invalid part: {s}")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fake;
+
+    impl Solution for Fake {
+        const DAY: u8 = 42;
+
+        type Answer1 = u64;
+        type Answer2 = &'static str;
+
+        fn part1(input: &str) -> u64 {
+            input.len() as u64
+        }
+
+        fn part2(_input: &str) -> &'static str {
+            "done"
+        }
+    }
+
+    #[test]
+    fn test_day_of_solution() {
+        let day = Day::of::<Fake>();
+
+        assert_eq!(42, day.number);
+        assert_eq!("3", (day.part1)("abc"));
+        assert_eq!("done", (day.part2)("abc"));
+        assert_eq!("3", day.solve(Part::One, "abc"));
+        assert_eq!("done", day.solve(Part::Two, "abc"));
+        assert_eq!(PathBuf::from("day42/input.txt"), day.input_path());
+        assert_eq!(PathBuf::from("day42/answers.toml"), day.answers_path());
+    }
+}
@@ -0,0 +1,65 @@
+//! The neighbour queries shared by [`Grid`](crate::Grid) and [`GridView`](crate::GridView), so
+//! that both find the same neighbours the same way.
+
+use crate::{Neighbour, Neighbourhood, Offset, Pos};
+
+/// A rectangle of cells, addressed by position or by flat, row-major index.
+pub(crate) trait Cells<T> {
+    fn cols(&self) -> usize;
+
+    fn cell(&self, pos: Pos) -> Option<&T>;
+
+    /// Move `pos` by `offset`, unless it would leave the cells.
+    fn step(&self, pos: Pos, offset: Offset) -> Option<Pos>;
+
+    fn neighbours(&self, pos: Pos, neighbourhood: &Neighbourhood) -> Vec<Neighbour> {
+        neighbours(pos, neighbourhood, |pos, offset| self.step(pos, offset))
+    }
+
+    fn neighbour_indices(&self, index: usize, neighbourhood: &Neighbourhood) -> Vec<usize> {
+        let cols = self.cols();
+
+        self.neighbours(Pos::from_index(index, cols), neighbourhood)
+            .into_iter()
+            .map(|neighbour| neighbour.pos.to_index(cols))
+            .collect()
+    }
+
+    fn neighbour_cells(&self, index: usize, neighbourhood: &Neighbourhood) -> Vec<(usize, T)>
+    where
+        T: Copy,
+    {
+        let cols = self.cols();
+
+        self.neighbour_indices(index, neighbourhood)
+            .into_iter()
+            .filter_map(|index| {
+                self.cell(Pos::from_index(index, cols))
+                    .map(|cell| (index, *cell))
+            })
+            .collect()
+    }
+
+    fn neighbour_cells_rc(&self, pos: Pos, neighbourhood: &Neighbourhood) -> Vec<(Neighbour, T)>
+    where
+        T: Copy,
+    {
+        self.neighbours(pos, neighbourhood)
+            .into_iter()
+            .filter_map(|neighbour| self.cell(neighbour.pos).map(|cell| (neighbour, *cell)))
+            .collect()
+    }
+}
+
+/// The positions around `pos` in `neighbourhood`, where `step` moves `pos` by an offset.
+pub(crate) fn neighbours(
+    pos: Pos,
+    neighbourhood: &Neighbourhood,
+    mut step: impl FnMut(Pos, Offset) -> Option<Pos>,
+) -> Vec<Neighbour> {
+    neighbourhood
+        .offsets()
+        .into_iter()
+        .filter_map(|offset| step(pos, offset).map(|pos| Neighbour { pos, offset }))
+        .collect()
+}
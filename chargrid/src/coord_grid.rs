@@ -0,0 +1,110 @@
+//! The queries by [`Coord`] which [`Grid`] and [`SparseGrid`] have in common, so that a solution
+//! can switch from one to the other without being rewritten.
+
+use crate::{BoundingBox, Coord, Grid, Neighbourhood, SparseGrid};
+
+/// A grid addressed by [`Coord`].
+pub trait CoordGrid<T> {
+    fn get_coord(&self, coord: Coord) -> Option<&T>;
+
+    /// The coordinates around `coord` in `neighbourhood`, whether there is a cell there or not.
+    fn adj_coords(&self, coord: Coord, neighbourhood: &Neighbourhood) -> Vec<Coord>;
+
+    /// The smallest rectangle containing every cell, unless there is none.
+    fn bounding_box(&self) -> Option<BoundingBox<Coord>>;
+
+    fn at_coord(&self, coord: Coord) -> Option<T>
+    where
+        T: Copy,
+    {
+        self.get_coord(coord).copied()
+    }
+
+    /// The cells around `coord` in `neighbourhood`.
+    fn adj_cells_coord(&self, coord: Coord, neighbourhood: &Neighbourhood) -> Vec<(Coord, T)>
+    where
+        T: Copy,
+    {
+        self.adj_coords(coord, neighbourhood)
+            .into_iter()
+            .filter_map(|coord| self.at_coord(coord).map(|cell| (coord, cell)))
+            .collect()
+    }
+}
+
+impl<T> CoordGrid<T> for Grid<T> {
+    fn get_coord(&self, coord: Coord) -> Option<&T> {
+        Grid::get_coord(self, coord)
+    }
+
+    fn adj_coords(&self, coord: Coord, neighbourhood: &Neighbourhood) -> Vec<Coord> {
+        Grid::adj_coords(self, coord, neighbourhood)
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox<Coord>> {
+        Grid::bounding_box(self).map(BoundingBox::from)
+    }
+}
+
+impl<T> CoordGrid<T> for SparseGrid<T> {
+    fn get_coord(&self, coord: Coord) -> Option<&T> {
+        SparseGrid::get_coord(self, coord)
+    }
+
+    fn adj_coords(&self, coord: Coord, neighbourhood: &Neighbourhood) -> Vec<Coord> {
+        SparseGrid::adj_coords(self, coord, neighbourhood)
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox<Coord>> {
+        SparseGrid::bounding_box(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::CharGrid;
+
+    use super::*;
+
+    /// The elves with at least two elves around them, written once for both kinds of grid.
+    fn crowded(grid: &impl CoordGrid<char>) -> Vec<Coord> {
+        let Some(bounding_box) = grid.bounding_box() else {
+            return Vec::new();
+        };
+
+        (bounding_box.min.row..=bounding_box.max.row)
+            .flat_map(|row| {
+                (bounding_box.min.col..=bounding_box.max.col).map(move |col| Coord::new(row, col))
+            })
+            .filter(|coord| grid.at_coord(*coord) == Some('#'))
+            .filter(|coord| {
+                grid.adj_cells_coord(*coord, &Neighbourhood::Moore)
+                    .into_iter()
+                    .filter(|(_coord, cell)| *cell == '#')
+                    .count()
+                    >= 2
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_answer_for_both_grids() {
+        let grid = CharGrid::from_str("##.\n#..\n..#\n").unwrap();
+        let sparse = SparseGrid::from_grid(&grid, |c| *c == '#');
+
+        let expected = vec![Coord::new(0, 0), Coord::new(0, 1), Coord::new(1, 0)];
+        assert_eq!(expected, crowded(&grid));
+        assert_eq!(expected, crowded(&sparse));
+
+        assert_eq!(
+            Some(BoundingBox {
+                min: Coord::new(0, 0),
+                max: Coord::new(2, 2),
+            }),
+            CoordGrid::bounding_box(&grid)
+        );
+        assert_eq!(None, CoordGrid::bounding_box(&CharGrid::new(3, Vec::new())));
+    }
+}
@@ -0,0 +1,500 @@
+use crate::{adjacency::Cells, Neighbour, Neighbourhood, Offset, Pos, Topology};
+
+/// A rectangular grid, stored row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    cols: usize,
+    cells: Vec<T>,
+    topology: Topology,
+}
+
+impl<T> Grid<T> {
+    /// Build a grid from its cells, row by row.
+    ///
+    /// Panics if `cells` cannot be split in rows of `cols` cells.
+    pub fn new(cols: usize, cells: Vec<T>) -> Self {
+        assert!(
+            cols > 0 && cells.len().is_multiple_of(cols),
+            "{} cells cannot fill rows of {cols} columns",
+            cells.len()
+        );
+
+        Self {
+            cols,
+            cells,
+            topology: Topology::Bounded,
+        }
+    }
+
+    /// Use `topology` to go past the edges of the grid.
+    pub fn with_topology(self, topology: Topology) -> Self {
+        Self { topology, ..self }
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.len().checked_div(self.cols).unwrap_or(0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        self.cells.get(pos)
+    }
+
+    pub fn is_end_of_row(&self, pos: usize) -> bool {
+        (pos + 1).is_multiple_of(self.cols) && pos < self.len()
+    }
+
+    /// The position of the cell at the flat index `pos`.
+    pub fn pos(&self, pos: usize) -> Pos {
+        Pos::from_index(pos, self.cols)
+    }
+
+    /// The flat index of `pos`, if it is inside the grid.
+    pub fn index(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos).then(|| pos.to_index(self.cols))
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.rows() && pos.col < self.cols
+    }
+
+    pub fn get_rc(&self, pos: Pos) -> Option<&T> {
+        self.index(pos).and_then(|index| self.cells.get(index))
+    }
+
+    /// Move `pos` by `offset`, unless it would leave the grid, whatever its topology.
+    pub fn checked_add(&self, pos: Pos, offset: Offset) -> Option<Pos> {
+        pos.checked_add(offset).filter(|pos| self.contains(*pos))
+    }
+
+    /// Like [`Grid::adj_pos_rc`], by index: always bounded, whatever the topology.
+    pub fn adj_pos(&self, pos: usize, neighbourhood: &Neighbourhood) -> Vec<usize> {
+        self.neighbour_indices(pos, neighbourhood)
+    }
+
+    /// The neighbours of `pos` inside the grid, moving like [`Grid::checked_add`].
+    ///
+    /// They stop at the edges even on a [`Topology::Torus`](crate::Topology::Torus), unlike the
+    /// searches and [`Grid::flood_fill`]: use [`Grid::adj_pos_wrapping`] or [`Grid::adj_coords`]
+    /// to follow the topology.
+    pub fn adj_pos_rc(&self, pos: Pos, neighbourhood: &Neighbourhood) -> Vec<Neighbour> {
+        self.neighbours(pos, neighbourhood)
+    }
+
+    pub fn cells(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// The rows of the grid, from top to bottom.
+    pub fn rows_iter(&self) -> std::slice::Chunks<'_, T> {
+        self.cells.chunks(self.cols)
+    }
+
+    /// The columns of the grid, from left to right, each from top to bottom.
+    pub fn cols_iter(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(|col| self.cells.iter().skip(col).step_by(self.cols))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        self.rows_iter().nth(row)
+    }
+
+    pub fn col(&self, col: usize) -> Option<impl Iterator<Item = &T>> {
+        (col < self.cols).then(|| self.cells.iter().skip(col).step_by(self.cols))
+    }
+
+    pub fn at_mut(&mut self, pos: usize) -> Option<&mut T> {
+        self.cells.get_mut(pos)
+    }
+
+    pub fn at_mut_rc(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index(pos).and_then(|index| self.cells.get_mut(index))
+    }
+
+    /// Replace the cell at `pos`, and return its previous value, unless `pos` is outside of the
+    /// grid.
+    pub fn set(&mut self, pos: usize, value: T) -> Option<T> {
+        self.at_mut(pos).map(|cell| std::mem::replace(cell, value))
+    }
+
+    pub fn set_rc(&mut self, pos: Pos, value: T) -> Option<T> {
+        self.at_mut_rc(pos)
+            .map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Exchange two cells.
+    ///
+    /// Panics if either position is outside of the grid.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.cells.swap(a, b);
+    }
+
+    /// Like [`Grid::swap`], by row and column.
+    pub fn swap_rc(&mut self, a: Pos, b: Pos) {
+        let a = self.index(a).expect("position outside of the grid");
+        let b = self.index(b).expect("position outside of the grid");
+
+        self.cells.swap(a, b);
+    }
+
+    /// Build a grid of the same shape by converting every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cols: self.cols,
+            cells: self.cells.iter().map(f).collect(),
+            topology: self.topology,
+        }
+    }
+
+    /// Like [`Grid::map`], but also give the position of each cell to `f`.
+    pub fn map_with_pos<U>(&self, mut f: impl FnMut(usize, &T) -> U) -> Grid<U> {
+        Grid {
+            cols: self.cols,
+            cells: self
+                .cells
+                .iter()
+                .enumerate()
+                .map(|(pos, cell)| f(pos, cell))
+                .collect(),
+            topology: self.topology,
+        }
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn at(&self, pos: usize) -> Option<T> {
+        self.cells.get(pos).copied()
+    }
+
+    pub fn at_rc(&self, row: usize, col: usize) -> Option<T> {
+        self.get_rc(Pos::new(row, col)).copied()
+    }
+
+    /// The values of the cells at [`Grid::adj_pos`], which ignores the topology.
+    pub fn adj_vals(&self, pos: usize, neighbourhood: &Neighbourhood) -> Vec<T> {
+        self.adj_cells(pos, neighbourhood)
+            .into_iter()
+            .map(|(_index, cell)| cell)
+            .collect()
+    }
+
+    /// The cells at [`Grid::adj_pos`], which ignores the topology: see
+    /// [`Grid::adj_cells_coord`] to follow it.
+    pub fn adj_cells(&self, pos: usize, neighbourhood: &Neighbourhood) -> Vec<(usize, T)> {
+        self.neighbour_cells(pos, neighbourhood)
+    }
+
+    /// The cells at [`Grid::adj_pos_rc`], which stop at the edges whatever the topology.
+    pub fn adj_cells_rc(&self, pos: Pos, neighbourhood: &Neighbourhood) -> Vec<(Neighbour, T)> {
+        self.neighbour_cells_rc(pos, neighbourhood)
+    }
+
+    /// The position of the first cell equal to `value`, in reading order.
+    pub fn find(&self, value: T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.cells
+            .iter()
+            .position(|cell| *cell == value)
+            .map(|index| self.pos(index))
+    }
+
+    /// The positions of all the cells equal to `value`, in reading order.
+    pub fn find_all(&self, value: T) -> Vec<Pos>
+    where
+        T: PartialEq,
+    {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_index, cell)| **cell == value)
+            .map(|(index, _cell)| self.pos(index))
+            .collect()
+    }
+}
+
+impl<T> Cells<T> for Grid<T> {
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn cell(&self, pos: Pos) -> Option<&T> {
+        self.get_rc(pos)
+    }
+
+    fn step(&self, pos: Pos, offset: Offset) -> Option<Pos> {
+        self.checked_add(pos, offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{CharGrid, Direction};
+
+    use super::*;
+
+    #[test]
+    fn char_grid() {
+        let source = "\
+abc
+123
+def
+456
+";
+
+        let grid = CharGrid::from_str(source).unwrap();
+
+        assert_eq!(12, grid.cells.len(), "cell count");
+        assert_eq!(3, grid.cols(), "column count");
+
+        assert_eq!(Some('a'), grid.at(0), "first cell");
+        assert_eq!(Some('c'), grid.at(2), "end of first line");
+        assert_eq!(Some('1'), grid.at(3), "start of second line");
+        assert_eq!(Some('6'), grid.at(11), "last cell");
+        assert_eq!(None, grid.at(12), "after last cell");
+
+        assert!(grid.is_end_of_row(2), "pos 2 is end of row");
+        assert!(grid.is_end_of_row(5), "pos 5 is end of row");
+        assert!(grid.is_end_of_row(8), "pos 8 is end of row");
+        assert!(grid.is_end_of_row(11), "pos 11 is end of row");
+
+        assert!(!grid.is_end_of_row(0), "pos 0 is NOT end of row");
+        assert!(!grid.is_end_of_row(3), "pos 3 is NOT end of row");
+        assert!(!grid.is_end_of_row(12), "pos 2 is NOT end of row");
+
+        assert_eq!(
+            vec!['b', '1', '2'],
+            grid.adj_vals(0, &Neighbourhood::Moore),
+            "adjacent cells of pos 0"
+        );
+
+        assert_eq!(Some('e'), grid.at(7));
+
+        assert_eq!(
+            vec!['1', '2', '3', 'd', 'f', '4', '5', '6'],
+            grid.adj_vals(7, &Neighbourhood::Moore),
+            "adjacent cells of pos 0"
+        );
+
+        assert_eq!(Some('5'), grid.at(10));
+
+        assert_eq!(
+            vec!['d', 'e', 'f', '4', '6'],
+            grid.adj_vals(10, &Neighbourhood::Moore),
+            "adjacent cells of pos 0"
+        );
+    }
+
+    #[test]
+    fn row_col_addressing() {
+        let grid = CharGrid::from_str("abc\n123\ndef\n456\n").unwrap();
+
+        assert_eq!(4, grid.rows());
+        assert_eq!(Pos::new(1, 2), grid.pos(5));
+        assert_eq!(Some(5), grid.index(Pos::new(1, 2)));
+        assert_eq!(None, grid.index(Pos::new(1, 3)), "past the last column");
+        assert_eq!(None, grid.index(Pos::new(4, 0)), "past the last row");
+
+        assert_eq!(Some('3'), grid.at_rc(1, 2));
+        assert_eq!(None, grid.at_rc(0, 3));
+
+        assert_eq!(
+            Some(Pos::new(0, 1)),
+            grid.checked_add(Pos::new(1, 2), Offset::new(-1, -1))
+        );
+        assert_eq!(None, grid.checked_add(Pos::new(1, 2), Offset::new(0, 1)));
+        assert_eq!(None, grid.checked_add(Pos::new(3, 0), Offset::new(1, 0)));
+    }
+
+    #[test]
+    fn adjacent_cells_at_edges() {
+        let grid = CharGrid::from_str("abc\n123\ndef\n456\n").unwrap();
+
+        assert_eq!(
+            vec!['b', '2', '3'],
+            grid.adj_vals(2, &Neighbourhood::Moore),
+            "top right corner"
+        );
+        assert_eq!(
+            vec!['2', '3', 'e', '5', '6'],
+            grid.adj_vals(8, &Neighbourhood::Moore),
+            "right edge"
+        );
+        assert_eq!(
+            vec!['d', 'e', '5'],
+            grid.adj_vals(9, &Neighbourhood::Moore),
+            "bottom left corner"
+        );
+        assert_eq!(
+            vec!['e', 'f', '5'],
+            grid.adj_vals(11, &Neighbourhood::Moore),
+            "bottom right corner"
+        );
+        assert_eq!(
+            vec!['a', 'b', '2', 'd', 'e'],
+            grid.adj_vals(3, &Neighbourhood::Moore),
+            "left edge"
+        );
+
+        let top_right: Vec<(Pos, char)> = grid
+            .adj_cells_rc(Pos::new(0, 2), &Neighbourhood::Moore)
+            .into_iter()
+            .map(|(neighbour, val)| (neighbour.pos, val))
+            .collect();
+        assert_eq!(
+            vec![
+                (Pos::new(0, 1), 'b'),
+                (Pos::new(1, 1), '2'),
+                (Pos::new(1, 2), '3')
+            ],
+            top_right
+        );
+
+        let single_column = CharGrid::from_str("a\nb\nc\n").unwrap();
+        assert_eq!(
+            vec!['a', 'c'],
+            single_column.adj_vals(1, &Neighbourhood::Moore)
+        );
+    }
+
+    #[test]
+    fn neighbourhoods() {
+        let grid = CharGrid::from_str("abc\n123\ndef\n456\n").unwrap();
+        let center = Pos::new(1, 1);
+
+        let vals = |neighbourhood| -> Vec<char> {
+            grid.adj_cells_rc(center, &neighbourhood)
+                .into_iter()
+                .map(|(_neighbour, val)| val)
+                .collect()
+        };
+
+        assert_eq!(
+            vec!['a', 'b', 'c', '1', '3', 'd', 'e', 'f'],
+            vals(Neighbourhood::Moore)
+        );
+        assert_eq!(vec!['b', '1', '3', 'e'], vals(Neighbourhood::VonNeumann));
+        assert_eq!(
+            vec!['b', '1', '3', 'e'],
+            grid.adj_vals(4, &Neighbourhood::VonNeumann),
+            "by flat index"
+        );
+        assert_eq!(vec![0, 2, 6, 8], grid.adj_pos(4, &Neighbourhood::Diagonal));
+        assert_eq!(vec!['a', 'c', 'd', 'f'], vals(Neighbourhood::Diagonal));
+        assert_eq!(
+            vec!['5'],
+            vals(Neighbourhood::Custom(vec![
+                Offset::new(2, 0),
+                Offset::new(0, 2)
+            ]))
+        );
+
+        let directions: Vec<Option<Direction>> = grid
+            .adj_pos_rc(Pos::new(0, 0), &Neighbourhood::VonNeumann)
+            .iter()
+            .map(Neighbour::direction)
+            .collect();
+        assert_eq!(
+            vec![Some(Direction::East), Some(Direction::South)],
+            directions
+        );
+
+        let far = grid.adj_pos_rc(center, &Neighbourhood::Custom(vec![Offset::new(2, 0)]));
+        assert_eq!(None, far[0].direction());
+    }
+
+    #[test]
+    fn map_grid() {
+        let grid = CharGrid::from_str("12\n34\n").unwrap();
+
+        let digits: Grid<u32> = grid.map(|c| c.to_digit(10).unwrap());
+        assert_eq!(Grid::new(2, vec![1, 2, 3, 4]), digits);
+        assert_eq!(2, digits.rows());
+
+        let weighted = digits.map_with_pos(|pos, digit| pos as u32 * digit);
+        assert_eq!(Grid::new(2, vec![0, 2, 6, 12]), weighted);
+
+        #[derive(Debug, PartialEq)]
+        enum Tile {
+            Odd,
+            Even,
+        }
+
+        let tiles = digits.map(|digit| {
+            if digit % 2 == 0 {
+                Tile::Even
+            } else {
+                Tile::Odd
+            }
+        });
+        assert_eq!(Some(&Tile::Odd), tiles.get(0));
+        assert_eq!(Some(&Tile::Even), tiles.get(3));
+        assert_eq!(None, tiles.get(4));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = CharGrid::from_str("abc\n123\n").unwrap();
+
+        let rows: Vec<String> = grid.rows_iter().map(|row| row.iter().collect()).collect();
+        assert_eq!(vec!["abc", "123"], rows);
+
+        let cols: Vec<String> = grid.cols_iter().map(|col| col.collect()).collect();
+        assert_eq!(vec!["a1", "b2", "c3"], cols);
+
+        assert_eq!(Some(&['1', '2', '3'][..]), grid.row(1));
+        assert_eq!(None, grid.row(2));
+        assert_eq!("c3", grid.col(2).unwrap().collect::<String>());
+        assert!(grid.col(3).is_none());
+
+        assert_eq!(0, Grid::<char>::new(3, Vec::new()).rows_iter().count());
+    }
+
+    #[test]
+    fn mutation() {
+        let mut grid = CharGrid::from_str("O.#\n.O.\n").unwrap();
+
+        assert_eq!(Some(Pos::new(0, 0)), grid.find('O'));
+        assert_eq!(vec![Pos::new(0, 0), Pos::new(1, 1)], grid.find_all('O'));
+        assert_eq!(None, grid.find('S'));
+
+        assert_eq!(Some('.'), grid.set(1, 'O'));
+        assert_eq!(Some('O'), grid.set_rc(Pos::new(0, 0), '.'));
+        assert_eq!(
+            None,
+            grid.set_rc(Pos::new(0, 3), 'O'),
+            "outside of the grid"
+        );
+
+        *grid.at_mut(2).unwrap() = '.';
+        grid.swap(1, 3);
+        grid.swap_rc(Pos::new(1, 1), Pos::new(1, 2));
+
+        assert_eq!(CharGrid::from_str("...\nO.O\n").unwrap(), grid);
+        assert_eq!(None, grid.at_mut(6));
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_grid_with_incomplete_row() {
+        Grid::new(2, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{Offset, Pos};
+
+/// A single step towards one of the 8 surrounding cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+use Direction::*;
+
+impl Direction {
+    /// All directions, in the reading order of the cells they lead to.
+    pub const ALL: [Direction; 8] = [
+        NorthWest, North, NorthEast, West, East, SouthWest, South, SouthEast,
+    ];
+
+    pub const ORTHOGONAL: [Direction; 4] = [North, West, East, South];
+
+    pub const DIAGONAL: [Direction; 4] = [NorthWest, NorthEast, SouthWest, SouthEast];
+
+    pub fn offset(self) -> Offset {
+        match self {
+            North => Offset::new(-1, 0),
+            NorthEast => Offset::new(-1, 1),
+            East => Offset::new(0, 1),
+            SouthEast => Offset::new(1, 1),
+            South => Offset::new(1, 0),
+            SouthWest => Offset::new(1, -1),
+            West => Offset::new(0, -1),
+            NorthWest => Offset::new(-1, -1),
+        }
+    }
+
+    pub fn from_offset(offset: Offset) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|direction| direction.offset() == offset)
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            North => South,
+            NorthEast => SouthWest,
+            East => West,
+            SouthEast => NorthWest,
+            South => North,
+            SouthWest => NorthEast,
+            West => East,
+            NorthWest => SouthEast,
+        }
+    }
+}
+
+/// Which cells count as neighbours of a cell.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Neighbourhood {
+    /// The 8 surrounding cells.
+    Moore,
+    /// The 4 orthogonal neighbours.
+    VonNeumann,
+    /// The 4 diagonal neighbours.
+    Diagonal,
+    /// Any stencil, which may reach further than the surrounding cells.
+    Custom(Vec<Offset>),
+}
+
+impl Neighbourhood {
+    pub fn offsets(&self) -> Vec<Offset> {
+        let directions: &[Direction] = match self {
+            Neighbourhood::Moore => &Direction::ALL,
+            Neighbourhood::VonNeumann => &Direction::ORTHOGONAL,
+            Neighbourhood::Diagonal => &Direction::DIAGONAL,
+            Neighbourhood::Custom(offsets) => return offsets.clone(),
+        };
+
+        directions
+            .iter()
+            .map(|direction| direction.offset())
+            .collect()
+    }
+}
+
+/// A cell reached from another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Neighbour {
+    pub pos: Pos,
+    pub offset: Offset,
+}
+
+impl Neighbour {
+    /// The direction the neighbour was reached by, unless it is further than one step away.
+    pub fn direction(&self) -> Option<Direction> {
+        Direction::from_offset(self.offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directions() {
+        for direction in Direction::ALL {
+            assert_eq!(Some(direction), Direction::from_offset(direction.offset()));
+            assert_eq!(direction, direction.opposite().opposite());
+            assert_ne!(direction, direction.opposite());
+        }
+
+        assert_eq!(None, Direction::from_offset(Offset::new(0, 2)));
+        assert_eq!(None, Direction::from_offset(Offset::new(0, 0)));
+    }
+
+    #[test]
+    fn neighbourhood_offsets() {
+        assert_eq!(8, Neighbourhood::Moore.offsets().len());
+
+        assert_eq!(
+            vec![
+                Offset::new(-1, 0),
+                Offset::new(0, -1),
+                Offset::new(0, 1),
+                Offset::new(1, 0)
+            ],
+            Neighbourhood::VonNeumann.offsets()
+        );
+
+        let knight_moves = vec![Offset::new(-2, 1), Offset::new(1, 2)];
+        assert_eq!(
+            knight_moves,
+            Neighbourhood::Custom(knight_moves.clone()).offsets()
+        );
+    }
+}
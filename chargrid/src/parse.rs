@@ -0,0 +1,205 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::Grid;
+
+/// Why a text could not be read as a grid. Line numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    /// There is no cell at all.
+    Empty,
+    /// A line does not have as many cells as the first one.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// A line ends with `\r`, and [`ParseOptions::crlf`] is not set.
+    CarriageReturn { line: usize },
+}
+
+impl std::error::Error for GridParseError {}
+
+impl Display for GridParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "empty grid"),
+            GridParseError::RaggedRow {
+                line,
+                expected,
+                actual,
+            } => write!(f, "line {line}: expected {expected} cells, found {actual}"),
+            GridParseError::CarriageReturn { line } => {
+                write!(f, "line {line}: unexpected carriage return")
+            }
+        }
+    }
+}
+
+/// What [`Grid::parse_with`] accepts on top of strictly rectangular, `\n`-terminated lines.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Accept `\r\n` line endings.
+    pub crlf: bool,
+    /// Ignore empty lines at the end of the text.
+    pub trim_trailing_empty_lines: bool,
+    /// Pad the lines shorter than the longest one with this cell, instead of failing.
+    pub pad: Option<char>,
+}
+
+impl Grid<char> {
+    /// Read a grid, one row per line, as allowed by `options`.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, GridParseError> {
+        let mut lines: Vec<&str> = s.split('\n').collect();
+
+        // The last line may be terminated by a newline, which does not start another line.
+        if s.ends_with('\n') {
+            lines.pop();
+        }
+
+        for (index, line) in lines.iter_mut().enumerate() {
+            if let Some(stripped) = line.strip_suffix('\r') {
+                if !options.crlf {
+                    return Err(GridParseError::CarriageReturn { line: index + 1 });
+                }
+
+                *line = stripped;
+            }
+        }
+
+        if options.trim_trailing_empty_lines {
+            while lines.last().is_some_and(|line| line.is_empty()) {
+                lines.pop();
+            }
+        }
+
+        let widths: Vec<usize> = lines.iter().map(|line| line.chars().count()).collect();
+
+        let cols = match options.pad {
+            Some(_) => widths.iter().copied().max().unwrap_or(0),
+            None => widths.first().copied().unwrap_or(0),
+        };
+
+        if let Some((index, actual)) = widths
+            .iter()
+            .enumerate()
+            .find(|(_index, width)| **width != cols && options.pad.is_none())
+        {
+            return Err(GridParseError::RaggedRow {
+                line: index + 1,
+                expected: cols,
+                actual: *actual,
+            });
+        }
+
+        if cols == 0 {
+            return Err(GridParseError::Empty);
+        }
+
+        let cells = lines
+            .iter()
+            .zip(widths)
+            .flat_map(|(line, width)| {
+                line.chars().chain(std::iter::repeat_n(
+                    options.pad.unwrap_or(' '),
+                    cols - width,
+                ))
+            })
+            .collect();
+
+        Ok(Grid::new(cols, cells))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridParseError;
+
+    /// Read a grid of rectangular, `\n`-terminated lines. See [`Grid::parse_with`] for more
+    /// lenient parsing.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, &ParseOptions::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CharGrid;
+
+    use super::*;
+
+    #[test]
+    fn strict_parsing() {
+        let grid = CharGrid::from_str("ab\ncd").unwrap();
+        assert_eq!(Grid::new(2, vec!['a', 'b', 'c', 'd']), grid);
+        assert_eq!(Ok(grid), CharGrid::from_str("ab\ncd\n"), "final newline");
+
+        assert_eq!(Err(GridParseError::Empty), CharGrid::from_str(""));
+        assert_eq!(Err(GridParseError::Empty), CharGrid::from_str("\n"));
+
+        assert_eq!(
+            Err(GridParseError::RaggedRow {
+                line: 2,
+                expected: 3,
+                actual: 2
+            }),
+            CharGrid::from_str("abc\nde\nfgh\n")
+        );
+        assert_eq!(
+            Err(GridParseError::RaggedRow {
+                line: 3,
+                expected: 2,
+                actual: 0
+            }),
+            CharGrid::from_str("ab\ncd\n\n"),
+            "trailing empty line"
+        );
+        assert_eq!(
+            Err(GridParseError::CarriageReturn { line: 1 }),
+            CharGrid::from_str("ab\r\ncd\r\n")
+        );
+
+        assert_eq!(
+            "line 2: expected 3 cells, found 2",
+            CharGrid::from_str("abc\nde\n").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn lenient_parsing() {
+        let expected = CharGrid::from_str("ab\ncd\n").unwrap();
+
+        let crlf = ParseOptions {
+            crlf: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Ok(&expected),
+            CharGrid::parse_with("ab\r\ncd\r\n", &crlf).as_ref()
+        );
+        assert_eq!(
+            Ok(&expected),
+            CharGrid::parse_with("ab\ncd\r\n", &crlf).as_ref()
+        );
+
+        let trim = ParseOptions {
+            trim_trailing_empty_lines: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Ok(&expected),
+            CharGrid::parse_with("ab\ncd\n\n\n", &trim).as_ref()
+        );
+        assert_eq!(
+            Err(GridParseError::Empty),
+            CharGrid::parse_with("\n\n", &trim)
+        );
+
+        let pad = ParseOptions {
+            pad: Some('.'),
+            ..Default::default()
+        };
+        assert_eq!(
+            CharGrid::from_str("a..\nbcd\n..."),
+            CharGrid::parse_with("a\nbcd\n\n", &pad)
+        );
+    }
+}
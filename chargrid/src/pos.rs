@@ -0,0 +1,111 @@
+/// The position of a cell, by row and column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Convert a flat, row-major index into a position in rows of `cols` cells.
+    pub fn from_index(index: usize, cols: usize) -> Self {
+        Self {
+            row: index / cols,
+            col: index % cols,
+        }
+    }
+
+    /// Convert the position into a flat, row-major index in rows of `cols` cells.
+    pub fn to_index(self, cols: usize) -> usize {
+        self.row * cols + self.col
+    }
+
+    /// Move by `offset`, unless it would go to a negative row or column.
+    pub fn checked_add(self, offset: Offset) -> Option<Self> {
+        Some(Self {
+            row: self.row.checked_add_signed(offset.row)?,
+            col: self.col.checked_add_signed(offset.col)?,
+        })
+    }
+}
+
+/// A signed move between two positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Offset {
+    pub row: isize,
+    pub col: isize,
+}
+
+impl Offset {
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+}
+
+/// The position of a cell by signed row and column, which may be outside of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Coord {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl Coord {
+    pub const fn new(row: i64, col: i64) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<Pos> for Coord {
+    fn from(pos: Pos) -> Self {
+        Self::new(pos.row as i64, pos.col as i64)
+    }
+}
+
+/// The position of a coordinate, unless its row or column is negative.
+impl TryFrom<Coord> for Pos {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(coord: Coord) -> Result<Self, Self::Error> {
+        Ok(Self::new(coord.row.try_into()?, coord.col.try_into()?))
+    }
+}
+
+impl std::ops::Add<Offset> for Coord {
+    type Output = Self;
+
+    fn add(self, offset: Offset) -> Self {
+        Self::new(self.row + offset.row as i64, self.col + offset.col as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pos_conversions() {
+        assert_eq!(Pos::new(0, 0), Pos::from_index(0, 3));
+        assert_eq!(Pos::new(0, 2), Pos::from_index(2, 3));
+        assert_eq!(Pos::new(3, 1), Pos::from_index(10, 3));
+
+        assert_eq!(10, Pos::new(3, 1).to_index(3));
+
+        assert_eq!(
+            Some(Pos::new(2, 2)),
+            Pos::new(3, 1).checked_add(Offset::new(-1, 1))
+        );
+        assert_eq!(None, Pos::new(3, 0).checked_add(Offset::new(0, -1)));
+        assert_eq!(None, Pos::new(0, 3).checked_add(Offset::new(-1, 0)));
+
+        assert_eq!(Ok(Pos::new(2, 3)), Pos::try_from(Coord::new(2, 3)));
+        assert!(Pos::try_from(Coord::new(2, -3)).is_err());
+
+        assert_eq!(
+            Coord::new(-1, 4),
+            Coord::from(Pos::new(0, 3)) + Offset::new(-1, 1)
+        );
+    }
+}
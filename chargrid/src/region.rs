@@ -0,0 +1,282 @@
+use std::collections::HashSet;
+
+use crate::{Coord, Direction, Grid, Neighbourhood, Offset, Pos};
+
+/// The smallest rectangle containing a set of cells, bounds included.
+///
+/// The cells are [`Pos`] in a grid, or [`Coord`] in a [`SparseGrid`](crate::SparseGrid).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox<P = Pos> {
+    pub min: P,
+    pub max: P,
+}
+
+impl<P> BoundingBox<P>
+where
+    P: Copy + Into<Coord> + TryFrom<Coord>,
+{
+    pub(crate) fn of(cells: impl Iterator<Item = P>) -> Option<Self> {
+        let mut coords = cells.map(Into::into);
+        let first: Coord = coords.next()?;
+
+        let (min, max) = coords.fold((first, first), |(min, max), coord| {
+            (
+                Coord::new(min.row.min(coord.row), min.col.min(coord.col)),
+                Coord::new(max.row.max(coord.row), max.col.max(coord.col)),
+            )
+        });
+
+        // Each bound takes its row and column from cells, so it is a valid cell too.
+        let cell = |coord| {
+            P::try_from(coord)
+                .ok()
+                .expect("the bound of cells is a cell")
+        };
+
+        Some(Self {
+            min: cell(min),
+            max: cell(max),
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        let (min, max): (Coord, Coord) = (self.min.into(), self.max.into());
+
+        (max.row - min.row + 1) as usize
+    }
+
+    pub fn cols(&self) -> usize {
+        let (min, max): (Coord, Coord) = (self.min.into(), self.max.into());
+
+        (max.col - min.col + 1) as usize
+    }
+
+    pub fn contains(&self, cell: P) -> bool {
+        let (min, max, cell): (Coord, Coord, Coord) =
+            (self.min.into(), self.max.into(), cell.into());
+
+        (min.row..=max.row).contains(&cell.row) && (min.col..=max.col).contains(&cell.col)
+    }
+}
+
+impl From<BoundingBox<Pos>> for BoundingBox<Coord> {
+    fn from(value: BoundingBox<Pos>) -> Self {
+        Self {
+            min: value.min.into(),
+            max: value.max.into(),
+        }
+    }
+}
+
+/// A connected set of cells.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+    cells: Vec<Pos>,
+    perimeter: usize,
+    bounding_box: BoundingBox,
+}
+
+impl Region {
+    /// The region of `cells`, where `step` moves a position by an offset in the grid.
+    fn new(mut cells: Vec<Pos>, step: impl Fn(Pos, Offset) -> Option<Pos>) -> Self {
+        cells.sort();
+
+        let members: HashSet<Pos> = cells.iter().copied().collect();
+
+        // Every side of a cell which does not touch another cell of the region is on the edge.
+        let perimeter = cells
+            .iter()
+            .flat_map(|pos| {
+                Direction::ORTHOGONAL
+                    .into_iter()
+                    .map(|direction| step(*pos, direction.offset()))
+            })
+            .filter(|neighbour| !neighbour.is_some_and(|pos| members.contains(&pos)))
+            .count();
+
+        let bounding_box =
+            BoundingBox::<Pos>::of(cells.iter().copied()).expect("a region is not empty");
+
+        Self {
+            cells,
+            perimeter,
+            bounding_box,
+        }
+    }
+
+    /// The cells of the region, in reading order.
+    pub fn cells(&self) -> &[Pos] {
+        &self.cells
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.binary_search(&pos).is_ok()
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cell sides between the region and the rest of the plane.
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.bounding_box
+    }
+}
+
+/// The connected components of a grid, and which one each cell belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Labelling {
+    /// The index in `regions` of the region of each cell, if any.
+    pub labels: Grid<Option<usize>>,
+    /// The regions, in the reading order of their first cell.
+    pub regions: Vec<Region>,
+}
+
+impl<T> Grid<T> {
+    /// The region of the cells matching `predicate` that are connected to `seed`, unless `seed`
+    /// does not match.
+    ///
+    /// `connectivity` is typically [`Neighbourhood::VonNeumann`] or [`Neighbourhood::Moore`].
+    pub fn flood_fill(
+        &self,
+        seed: Pos,
+        connectivity: &Neighbourhood,
+        mut predicate: impl FnMut(&T) -> bool,
+    ) -> Option<Region> {
+        if !self.get_rc(seed).is_some_and(&mut predicate) {
+            return None;
+        }
+
+        let result = self.bfs(seed, connectivity, predicate);
+
+        Some(Region::new(
+            result.visited().copied().collect(),
+            |pos, offset| self.wrapping_add(pos, offset),
+        ))
+    }
+
+    /// Split the cells matching `predicate` into connected regions.
+    pub fn components(
+        &self,
+        connectivity: &Neighbourhood,
+        mut predicate: impl FnMut(&T) -> bool,
+    ) -> Labelling {
+        let mut labels = vec![None; self.len()];
+        let mut regions = Vec::new();
+
+        for index in 0..self.len() {
+            if labels[index].is_some() {
+                continue;
+            }
+
+            let Some(region) = self.flood_fill(self.pos(index), connectivity, &mut predicate)
+            else {
+                continue;
+            };
+
+            for pos in region.cells() {
+                labels[pos.to_index(self.cols())] = Some(regions.len());
+            }
+
+            regions.push(region);
+        }
+
+        Labelling {
+            labels: self.map_with_pos(|index, _cell| labels[index]),
+            regions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::CharGrid;
+
+    use super::*;
+
+    #[test]
+    fn flood_fill() {
+        let grid = CharGrid::from_str(
+            "\
+AAAA
+BBCD
+BBCC
+EEEC
+",
+        )
+        .unwrap();
+
+        let region = grid
+            .flood_fill(Pos::new(1, 2), &Neighbourhood::VonNeumann, |c| *c == 'C')
+            .unwrap();
+
+        assert_eq!(
+            &[
+                Pos::new(1, 2),
+                Pos::new(2, 2),
+                Pos::new(2, 3),
+                Pos::new(3, 3)
+            ],
+            region.cells()
+        );
+        assert_eq!(4, region.area());
+        assert_eq!(10, region.perimeter());
+        assert_eq!(
+            BoundingBox {
+                min: Pos::new(1, 2),
+                max: Pos::new(3, 3)
+            },
+            region.bounding_box()
+        );
+        assert!(region.contains(Pos::new(2, 3)));
+        assert!(!region.contains(Pos::new(1, 3)));
+
+        let region = grid
+            .flood_fill(Pos::new(0, 0), &Neighbourhood::VonNeumann, |c| *c == 'A')
+            .unwrap();
+        assert_eq!((4, 10), (region.area(), region.perimeter()));
+        assert_eq!(
+            (1, 4),
+            (region.bounding_box().rows(), region.bounding_box().cols())
+        );
+
+        assert_eq!(
+            None,
+            grid.flood_fill(Pos::new(0, 0), &Neighbourhood::VonNeumann, |c| *c == 'C'),
+            "the seed does not match"
+        );
+    }
+
+    #[test]
+    fn components() {
+        let grid = CharGrid::from_str(
+            "\
+#..#
+.#..
+..#.
+",
+        )
+        .unwrap();
+
+        let labelling = grid.components(&Neighbourhood::VonNeumann, |c| *c == '#');
+        assert_eq!(4, labelling.regions.len());
+        assert_eq!(Some(&Some(1)), labelling.labels.get_rc(Pos::new(0, 3)));
+        assert_eq!(Some(&None), labelling.labels.get_rc(Pos::new(0, 1)));
+
+        let labelling = grid.components(&Neighbourhood::Moore, |c| *c == '#');
+        assert_eq!(2, labelling.regions.len());
+
+        let diagonal = &labelling.regions[0];
+        assert_eq!(3, diagonal.area());
+        assert_eq!(12, diagonal.perimeter());
+        assert_eq!(Pos::new(2, 2), diagonal.bounding_box().max);
+        assert_eq!(Some(&Some(0)), labelling.labels.get_rc(Pos::new(2, 2)));
+        assert_eq!(Some(&Some(1)), labelling.labels.get_rc(Pos::new(0, 3)));
+    }
+}
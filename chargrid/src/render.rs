@@ -0,0 +1,175 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{CharGrid, Grid, Pos};
+
+/// Print the grid row by row, each row on its own line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows_iter() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A terminal colour, as an ANSI escape code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Colour {
+    fn code(&self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+        }
+    }
+}
+
+/// How an [`Overlay`] shows a highlighted cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    /// Print this instead of the cell.
+    Char(char),
+    /// Print the cell in this colour.
+    Colour(Colour),
+}
+
+/// A grid printed with some of its cells highlighted, such as a path found by a search.
+pub struct Overlay<'a, T> {
+    grid: &'a Grid<T>,
+    highlights: HashMap<Pos, Highlight>,
+}
+
+impl<T> Grid<T> {
+    /// Start highlighting cells of the grid for printing.
+    pub fn overlay(&self) -> Overlay<'_, T> {
+        Overlay {
+            grid: self,
+            highlights: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Overlay<'_, T> {
+    /// Highlight the cells at `positions`, instead of any way they were highlighted before.
+    pub fn highlight(mut self, positions: impl IntoIterator<Item = Pos>, how: Highlight) -> Self {
+        self.highlights
+            .extend(positions.into_iter().map(|pos| (pos, how)));
+        self
+    }
+}
+
+impl<T: Display> Display for Overlay<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, cell) in self.grid.cells().enumerate() {
+            match self.highlights.get(&self.grid.pos(index)) {
+                None => write!(f, "{cell}")?,
+                Some(Highlight::Char(c)) => write!(f, "{c}")?,
+                Some(Highlight::Colour(colour)) => {
+                    write!(f, "\x1b[{}m{cell}\x1b[0m", colour.code())?
+                }
+            }
+
+            if self.grid.is_end_of_row(index) {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The shades of a heatmap, from the lowest value to the highest.
+const SHADES: [char; 9] = ['.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+impl Grid<Option<u64>> {
+    /// Shade every cell by its value, from `.` for the lowest to `@` for the highest, leaving
+    /// the cells without a value blank. Meant for the distance maps of searches.
+    pub fn heatmap(&self) -> CharGrid {
+        let values = self.cells().flatten();
+        let min = values.clone().min().copied().unwrap_or(0);
+        let max = values.max().copied().unwrap_or(0);
+
+        self.map(|cell| match cell {
+            None => ' ',
+            Some(_) if max == min => SHADES[0],
+            Some(value) => {
+                let shade = (value - min) * (SHADES.len() as u64 - 1) / (max - min);
+                SHADES[shade as usize]
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::Neighbourhood;
+
+    use super::*;
+
+    #[test]
+    fn display() {
+        let source = "#.#\n.S.\n";
+        let grid = CharGrid::from_str(source).unwrap();
+
+        assert_eq!(source, grid.to_string());
+        assert_eq!("12\n34\n", Grid::new(2, vec![1, 2, 3, 4]).to_string());
+    }
+
+    #[test]
+    fn overlay() {
+        let grid = CharGrid::from_str("S..\n##.\n..E\n").unwrap();
+        let path = grid
+            .bfs(Pos::new(0, 0), &Neighbourhood::VonNeumann, |c| *c != '#')
+            .path_to(&Pos::new(2, 2))
+            .unwrap();
+
+        assert_eq!(
+            "Soo\n##o\n..E\n",
+            grid.overlay()
+                .highlight(
+                    path[1..path.len() - 1].iter().copied(),
+                    Highlight::Char('o')
+                )
+                .to_string()
+        );
+
+        assert_eq!(
+            "S..\n\x1b[31m#\x1b[0m#.\n..E\n",
+            grid.overlay()
+                .highlight([Pos::new(1, 0)], Highlight::Char('x'))
+                .highlight([Pos::new(1, 0)], Highlight::Colour(Colour::Red))
+                .to_string(),
+            "the last highlight wins"
+        );
+    }
+
+    #[test]
+    fn heatmap() {
+        let distances = Grid::new(3, vec![Some(0), Some(1), None, Some(8), None, Some(4)]);
+        assert_eq!(".: \n@ +\n", distances.heatmap().to_string());
+
+        assert_eq!(
+            ". \n",
+            Grid::new(2, vec![Some(3), None]).heatmap().to_string()
+        );
+    }
+}
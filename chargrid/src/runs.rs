@@ -0,0 +1,136 @@
+use crate::{Grid, Pos};
+
+/// A maximal horizontal run of matching cells, such as a number in a line of text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub row: usize,
+    /// The column of the first cell.
+    pub start: usize,
+    /// The column after the last cell.
+    pub end: usize,
+    pub text: String,
+}
+
+impl Span {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        (self.start..self.end).map(|col| Pos::new(self.row, col))
+    }
+}
+
+impl Grid<char> {
+    /// Find every maximal run of cells matching `predicate`, row by row. Runs never continue on
+    /// the next row.
+    pub fn runs(&self, mut predicate: impl FnMut(char) -> bool) -> Vec<Span> {
+        let mut runs = Vec::new();
+
+        for row in 0..self.rows() {
+            let mut current: Option<Span> = None;
+
+            for col in 0..self.cols() {
+                let cell = self.at_rc(row, col).unwrap();
+
+                if !predicate(cell) {
+                    runs.extend(current.take());
+                    continue;
+                }
+
+                let span = current.get_or_insert_with(|| Span {
+                    row,
+                    start: col,
+                    end: col,
+                    text: String::new(),
+                });
+
+                span.end = col + 1;
+                span.text.push(cell);
+            }
+
+            runs.extend(current);
+        }
+
+        runs
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    /// The cells around `span`, diagonals included, in reading order.
+    pub fn span_neighbours(&self, span: &Span) -> Vec<(Pos, T)> {
+        let rows = span.row.saturating_sub(1)..=span.row + 1;
+        let cols = span.start.saturating_sub(1)..=span.end;
+
+        rows.flat_map(|row| cols.clone().map(move |col| Pos::new(row, col)))
+            .filter(|pos| pos.row != span.row || !(span.start..span.end).contains(&pos.col))
+            .filter_map(|pos| self.get_rc(pos).map(|cell| (pos, *cell)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::CharGrid;
+
+    use super::*;
+
+    #[test]
+    fn runs() {
+        let grid = CharGrid::from_str(
+            "\
+467..114
+...*....
+..35..63
+3.......
+",
+        )
+        .unwrap();
+
+        let numbers = grid.runs(|c| c.is_ascii_digit());
+
+        let texts: Vec<&str> = numbers.iter().map(|span| span.text.as_str()).collect();
+        assert_eq!(
+            vec!["467", "114", "35", "63", "3"],
+            texts,
+            "no run across rows"
+        );
+
+        assert_eq!(
+            Span {
+                row: 2,
+                start: 6,
+                end: 8,
+                text: String::from("63")
+            },
+            numbers[3]
+        );
+        assert_eq!(2, numbers[3].len());
+        assert_eq!(
+            vec![Pos::new(2, 6), Pos::new(2, 7)],
+            numbers[3].positions().collect::<Vec<_>>()
+        );
+
+        let around_467: String = grid
+            .span_neighbours(&numbers[0])
+            .into_iter()
+            .map(|(_pos, c)| c)
+            .collect();
+        assert_eq!("....*", around_467, "top left corner");
+
+        let around_35: Vec<(Pos, char)> = grid
+            .span_neighbours(&numbers[2])
+            .into_iter()
+            .filter(|(_pos, c)| *c != '.')
+            .collect();
+        assert_eq!(vec![(Pos::new(1, 3), '*')], around_35);
+
+        assert_eq!(7, grid.span_neighbours(&numbers[3]).len(), "right edge");
+    }
+}
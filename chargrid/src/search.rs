@@ -0,0 +1,455 @@
+//! Graph searches over any state space, with shortcuts to search between the cells of a grid.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use crate::{Grid, Neighbourhood, Pos, Topology};
+
+/// What a search found: the distance to every visited state, and how it was reached.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S> {
+    distances: HashMap<S, u64>,
+    parents: HashMap<S, S>,
+    goal: Option<S>,
+}
+
+impl<S: Hash + Eq + Clone> SearchResult<S> {
+    fn new(start: S) -> Self {
+        Self {
+            distances: HashMap::from([(start, 0)]),
+            parents: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    pub fn distance(&self, state: &S) -> Option<u64> {
+        self.distances.get(state).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<S, u64> {
+        &self.distances
+    }
+
+    pub fn visited(&self) -> impl Iterator<Item = &S> + '_ {
+        self.distances.keys()
+    }
+
+    pub fn is_visited(&self, state: &S) -> bool {
+        self.distances.contains_key(state)
+    }
+
+    /// The first goal state the search reached, if any.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    /// The states from the start to `state` included, if `state` was visited.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.is_visited(state) {
+            return None;
+        }
+
+        let mut path = vec![state.clone()];
+
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// The states from the start to the goal included, if a goal was reached.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+}
+
+impl SearchResult<Pos> {
+    /// The distance to each cell of `grid`, or `None` for the cells the search did not reach.
+    pub fn distance_map<T>(&self, grid: &Grid<T>) -> Grid<Option<u64>> {
+        grid.map_with_pos(|index, _cell| self.distance(&grid.pos(index)))
+    }
+}
+
+/// Breadth-first search from `start`, until a state satisfying `is_goal` is reached or every
+/// reachable state has been visited.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult::new(start.clone());
+    let mut pending = VecDeque::from([(start, 0)]);
+
+    while let Some((state, distance)) = pending.pop_front() {
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+
+        for next in successors(&state) {
+            if result.is_visited(&next) {
+                continue;
+            }
+
+            result.distances.insert(next.clone(), distance + 1);
+            result.parents.insert(next.clone(), state.clone());
+            pending.push_back((next, distance + 1));
+        }
+    }
+
+    result
+}
+
+/// Cheapest-path search from `start`, where `successors` gives each next state with the cost of
+/// moving to it. Only the states whose distance is final are reported as visited.
+pub fn dijkstra<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(start, successors, |_state| 0, is_goal)
+}
+
+/// Like [`dijkstra`], but explore first the states that `heuristic` estimates to be closest to a
+/// goal. The estimate must never exceed the actual remaining cost for the result to be exact.
+pub fn astar<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> u64,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut result = SearchResult {
+        distances: HashMap::new(),
+        parents: HashMap::new(),
+        goal: None,
+    };
+
+    let mut best_costs = HashMap::from([(start.clone(), 0)]);
+    let mut pending = BinaryHeap::from([Pending {
+        priority: heuristic(&start),
+        cost: 0,
+        state: start,
+    }]);
+
+    while let Some(Pending { cost, state, .. }) = pending.pop() {
+        if result.is_visited(&state) {
+            continue;
+        }
+
+        result.distances.insert(state.clone(), cost);
+
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+
+            if result.is_visited(&next)
+                || best_costs.get(&next).is_some_and(|best| *best <= next_cost)
+            {
+                continue;
+            }
+
+            best_costs.insert(next.clone(), next_cost);
+            result.parents.insert(next.clone(), state.clone());
+            pending.push(Pending {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    result
+}
+
+/// A state waiting to be visited, ordered so that the max-heap pops the lowest priority first.
+struct Pending<S> {
+    priority: u64,
+    cost: u64,
+    state: S,
+}
+
+impl<S> PartialEq for Pending<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S> Eq for Pending<S> {}
+
+impl<S> PartialOrd for Pending<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Pending<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// The searches over the cells of a grid move like [`Grid::wrapping_add`], so they wrap around the
+/// edges of a [`Topology::Torus`].
+impl<T> Grid<T> {
+    /// Visit every cell reachable from `start` through `passable` cells.
+    pub fn bfs(
+        &self,
+        start: Pos,
+        neighbourhood: &Neighbourhood,
+        mut passable: impl FnMut(&T) -> bool,
+    ) -> SearchResult<Pos> {
+        bfs(
+            start,
+            |pos| {
+                self.adj_pos_wrapping(*pos, neighbourhood)
+                    .into_iter()
+                    .map(|neighbour| neighbour.pos)
+                    .filter(|pos| self.get_rc(*pos).is_some_and(&mut passable))
+                    .collect::<Vec<_>>()
+            },
+            |_pos| false,
+        )
+    }
+
+    /// Find the cheapest path from `start` to every cell, where `cost` gives the cost of entering
+    /// a cell, or `None` if it cannot be entered.
+    pub fn dijkstra(
+        &self,
+        start: Pos,
+        neighbourhood: &Neighbourhood,
+        cost: impl FnMut(&T) -> Option<u64>,
+    ) -> SearchResult<Pos> {
+        self.cheapest_path(start, None, neighbourhood, cost)
+    }
+
+    /// Find the cheapest path from `start` to `goal`, where `cost` gives the cost of entering a
+    /// cell, or `None` if it cannot be entered. Costs must be at least 1.
+    pub fn astar(
+        &self,
+        start: Pos,
+        goal: Pos,
+        neighbourhood: &Neighbourhood,
+        cost: impl FnMut(&T) -> Option<u64>,
+    ) -> SearchResult<Pos> {
+        self.cheapest_path(start, Some(goal), neighbourhood, cost)
+    }
+
+    fn cheapest_path(
+        &self,
+        start: Pos,
+        goal: Option<Pos>,
+        neighbourhood: &Neighbourhood,
+        mut cost: impl FnMut(&T) -> Option<u64>,
+    ) -> SearchResult<Pos> {
+        // On a torus, the shortest way may go around the edges.
+        let sizes = match self.topology() {
+            Topology::Torus => Some((self.rows(), self.cols())),
+            Topology::Bounded | Topology::InfiniteTiling => None,
+        };
+
+        let heuristic = |pos: &Pos| match goal {
+            Some(goal) => min_steps(*pos, goal, sizes, neighbourhood),
+            None => 0,
+        };
+
+        astar(
+            start,
+            |pos| {
+                self.adj_pos_wrapping(*pos, neighbourhood)
+                    .into_iter()
+                    .filter_map(|neighbour| {
+                        let cell = self.get_rc(neighbour.pos)?;
+                        Some((neighbour.pos, cost(cell)?))
+                    })
+                    .collect::<Vec<_>>()
+            },
+            heuristic,
+            |pos| Some(*pos) == goal,
+        )
+    }
+}
+
+/// A lower bound of the number of steps from `from` to `to` in `neighbourhood`, when the rows
+/// and columns wrap around after the given numbers of cells.
+fn min_steps(
+    from: Pos,
+    to: Pos,
+    wrapping: Option<(usize, usize)>,
+    neighbourhood: &Neighbourhood,
+) -> u64 {
+    let (mut rows, mut cols) = (from.row.abs_diff(to.row), from.col.abs_diff(to.col));
+
+    if let Some((row_count, col_count)) = wrapping {
+        rows = rows.min(row_count - rows);
+        cols = cols.min(col_count - cols);
+    }
+
+    let (rows, cols) = (rows as u64, cols as u64);
+
+    match neighbourhood {
+        Neighbourhood::VonNeumann => rows + cols,
+        Neighbourhood::Moore | Neighbourhood::Diagonal => rows.max(cols),
+        Neighbourhood::Custom(_) => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{CharGrid, Direction};
+
+    use super::*;
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.##...#E
+";
+
+    fn find(grid: &CharGrid, c: char) -> Pos {
+        grid.pos(grid.cells().position(|cell| *cell == c).unwrap())
+    }
+
+    #[test]
+    fn bfs_on_grid() {
+        let grid = CharGrid::from_str(MAZE).unwrap();
+        let start = find(&grid, 'S');
+        let end = find(&grid, 'E');
+
+        let result = grid.bfs(start, &Neighbourhood::VonNeumann, |c| *c != '#');
+
+        assert_eq!(Some(12), result.distance(&end));
+        assert_eq!(Some(0), result.distance(&start));
+        assert_eq!(None, result.distance(&Pos::new(0, 2)), "wall");
+        assert!(!result.is_visited(&Pos::new(1, 2)), "wall");
+
+        let path = result.path_to(&end).unwrap();
+        assert_eq!(13, path.len());
+        assert_eq!(start, path[0]);
+        assert_eq!(end, path[12]);
+
+        let distances = result.distance_map(&grid);
+        assert_eq!(Some(&Some(12)), distances.get_rc(end));
+        assert_eq!(Some(&None), distances.get_rc(Pos::new(0, 2)));
+
+        let diagonal = grid.bfs(start, &Neighbourhood::Moore, |c| *c != '#');
+        assert_eq!(Some(8), diagonal.distance(&end));
+    }
+
+    #[test]
+    fn weighted_search_on_grid() {
+        let grid = CharGrid::from_str(
+            "\
+19111
+11191
+99111
+",
+        )
+        .unwrap()
+        .map(|c| c.to_digit(10).unwrap() as u64);
+
+        let start = Pos::new(0, 0);
+        let goal = Pos::new(2, 4);
+
+        let result = grid.dijkstra(start, &Neighbourhood::VonNeumann, |cost| Some(*cost));
+        assert_eq!(Some(6), result.distance(&goal));
+
+        let result = grid.astar(start, goal, &Neighbourhood::VonNeumann, |cost| Some(*cost));
+        assert_eq!(Some(6), result.distance(&goal));
+        assert_eq!(Some(&goal), result.goal());
+        assert_eq!(7, result.path().unwrap().len());
+
+        let walled = grid.astar(start, Pos::new(2, 0), &Neighbourhood::VonNeumann, |cost| {
+            (*cost < 9).then_some(*cost)
+        });
+        assert_eq!(None, walled.goal(), "the goal cannot be entered");
+    }
+
+    #[test]
+    fn weighted_search_on_torus() {
+        let grid = Grid::new(10, vec![1_u64; 10]).with_topology(Topology::Torus);
+        let (start, goal) = (Pos::new(0, 1), Pos::new(0, 8));
+
+        let result = grid.dijkstra(start, &Neighbourhood::VonNeumann, |cost| Some(*cost));
+        assert_eq!(Some(3), result.distance(&goal), "around the edges");
+
+        for neighbourhood in [Neighbourhood::VonNeumann, Neighbourhood::Moore] {
+            let result = grid.astar(start, goal, &neighbourhood, |cost| Some(*cost));
+            assert_eq!(Some(3), result.distance(&goal), "{neighbourhood:?}");
+        }
+    }
+
+    #[test]
+    fn search_over_states() {
+        // The crucibles of AoC 2023 day 17, which go at most 3 cells straight.
+        let grid = CharGrid::from_str(
+            "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+",
+        )
+        .unwrap()
+        .map(|c| c.to_digit(10).unwrap() as u64);
+
+        let goal = Pos::new(grid.rows() - 1, grid.cols() - 1);
+
+        let result = dijkstra(
+            (Pos::new(0, 0), Direction::East, 0),
+            |(pos, direction, straight)| {
+                Direction::ORTHOGONAL
+                    .into_iter()
+                    .filter(|next| *next != direction.opposite())
+                    .filter(|next| next != direction || *straight < 3)
+                    .filter_map(|next| {
+                        let next_pos = grid.checked_add(*pos, next.offset())?;
+                        let next_straight = if next == *direction { straight + 1 } else { 1 };
+
+                        Some((
+                            (next_pos, next, next_straight),
+                            *grid.get_rc(next_pos).unwrap(),
+                        ))
+                    })
+                    .collect::<Vec<_>>()
+            },
+            |(pos, _, _)| *pos == goal,
+        );
+
+        let goal_state = result.goal().unwrap();
+        assert_eq!(Some(102), result.distance(goal_state));
+    }
+}
@@ -0,0 +1,232 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{BoundingBox, Coord, CoordGrid, Grid, Neighbourhood};
+
+/// A grid of which only a few cells are occupied, over an unbounded plane.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Coord, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the cells of `grid` matching `occupied`, at the same row and column.
+    pub fn from_grid(grid: &Grid<T>, occupied: impl FnMut(&T) -> bool) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_grid_at(Coord::new(0, 0), grid, occupied)
+    }
+
+    /// Keep the cells of `grid` matching `occupied`, with its first cell at `origin`.
+    pub fn from_grid_at(origin: Coord, grid: &Grid<T>, mut occupied: impl FnMut(&T) -> bool) -> Self
+    where
+        T: Clone,
+    {
+        grid.cells()
+            .enumerate()
+            .filter(|(_index, cell)| occupied(cell))
+            .map(|(index, cell)| {
+                let pos = grid.pos(index);
+                let coord = Coord::new(origin.row + pos.row as i64, origin.col + pos.col as i64);
+
+                (coord, cell.clone())
+            })
+            .collect()
+    }
+
+    /// The number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.cells.contains_key(&coord)
+    }
+
+    pub fn get_coord(&self, coord: Coord) -> Option<&T> {
+        self.cells.get(&coord)
+    }
+
+    pub fn get_coord_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.cells.get_mut(&coord)
+    }
+
+    /// Occupy the cell at `coord`, and return its previous value, if any.
+    pub fn insert(&mut self, coord: Coord, value: T) -> Option<T> {
+        self.cells.insert(coord, value)
+    }
+
+    pub fn remove(&mut self, coord: Coord) -> Option<T> {
+        self.cells.remove(&coord)
+    }
+
+    /// The occupied cells, in no particular order.
+    pub fn cells(&self) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        self.cells.iter().map(|(coord, cell)| (*coord, cell))
+    }
+
+    /// The coordinates around `coord` in `neighbourhood`, whether they are occupied or not.
+    pub fn adj_coords(&self, coord: Coord, neighbourhood: &Neighbourhood) -> Vec<Coord> {
+        neighbourhood
+            .offsets()
+            .into_iter()
+            .map(|offset| coord + offset)
+            .collect()
+    }
+
+    /// The smallest rectangle containing every occupied cell, unless there is none.
+    pub fn bounding_box(&self) -> Option<BoundingBox<Coord>> {
+        BoundingBox::<Coord>::of(self.cells.keys().copied())
+    }
+
+    /// Build a dense grid covering the bounding box, filling the cells that are not occupied with
+    /// `fill`, and give the coordinate of its first cell, the top left corner of the bounding box.
+    ///
+    /// [`SparseGrid::from_grid_at`] with that origin gives back the same cells.
+    pub fn to_grid(&self, fill: T) -> Option<(Coord, Grid<T>)>
+    where
+        T: Clone,
+    {
+        let bounding_box = self.bounding_box()?;
+
+        let cells = (bounding_box.min.row..=bounding_box.max.row)
+            .flat_map(|row| {
+                (bounding_box.min.col..=bounding_box.max.col).map(move |col| Coord::new(row, col))
+            })
+            .map(|coord| self.cells.get(&coord).unwrap_or(&fill).clone())
+            .collect();
+
+        Some((bounding_box.min, Grid::new(bounding_box.cols(), cells)))
+    }
+}
+
+impl<T: Copy> SparseGrid<T> {
+    pub fn at_coord(&self, coord: Coord) -> Option<T> {
+        self.get_coord(coord).copied()
+    }
+
+    /// The occupied cells around `coord` in `neighbourhood`.
+    pub fn adj_cells_coord(&self, coord: Coord, neighbourhood: &Neighbourhood) -> Vec<(Coord, T)> {
+        CoordGrid::adj_cells_coord(self, coord, neighbourhood)
+    }
+}
+
+impl<T> FromIterator<(Coord, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Coord, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Print the bounding box row by row like a [`Grid`], with `.` for the cells that are not
+/// occupied.
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(bounding_box) = self.bounding_box() else {
+            return Ok(());
+        };
+
+        for row in bounding_box.min.row..=bounding_box.max.row {
+            for col in bounding_box.min.col..=bounding_box.max.col {
+                match self.cells.get(&Coord::new(row, col)) {
+                    Some(cell) => write!(f, "{cell}")?,
+                    None => write!(f, ".")?,
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::CharGrid;
+
+    use super::*;
+
+    #[test]
+    fn sparse_grid() {
+        let mut elves: SparseGrid<char> = [Coord::new(-2, 5), Coord::new(1_000, -3)]
+            .into_iter()
+            .map(|coord| (coord, '#'))
+            .collect();
+
+        assert_eq!(2, elves.len());
+        assert_eq!(Some('#'), elves.at_coord(Coord::new(1_000, -3)));
+        assert_eq!(None, elves.at_coord(Coord::new(0, 0)));
+
+        let bounding_box = elves.bounding_box().unwrap();
+        assert_eq!(
+            (Coord::new(-2, -3), Coord::new(1_000, 5)),
+            (bounding_box.min, bounding_box.max)
+        );
+        assert_eq!((1_003, 9), (bounding_box.rows(), bounding_box.cols()));
+
+        assert_eq!(Some('#'), elves.remove(Coord::new(1_000, -3)));
+        assert_eq!(None, elves.insert(Coord::new(-1, 4), '#'));
+        assert_eq!(
+            vec![(Coord::new(-2, 5), '#')],
+            elves.adj_cells_coord(Coord::new(-1, 4), &Neighbourhood::Moore)
+        );
+        assert_eq!(
+            4,
+            elves
+                .adj_coords(Coord::new(-1, 4), &Neighbourhood::VonNeumann)
+                .len()
+        );
+
+        assert_eq!(".#\n#.\n", elves.to_string());
+        assert_eq!("", SparseGrid::<char>::new().to_string());
+    }
+
+    #[test]
+    fn conversions() {
+        let grid = CharGrid::from_str("#..\n..#\n").unwrap();
+
+        let sparse = SparseGrid::from_grid(&grid, |c| *c == '#');
+        assert_eq!(2, sparse.len());
+        assert_eq!(Some('#'), sparse.at_coord(Coord::new(1, 2)));
+        assert_eq!(grid.to_string(), sparse.to_string());
+        assert_eq!(Some((Coord::new(0, 0), grid)), sparse.to_grid('.'));
+
+        // The bounding box does not start at the first cell of the grid.
+        let grid = CharGrid::from_str(".#.\n..#\n").unwrap();
+        let sparse = SparseGrid::from_grid(&grid, |c| *c == '#');
+
+        let (origin, dense) = sparse.to_grid('.').unwrap();
+        assert_eq!(Coord::new(0, 1), origin);
+        assert_eq!(CharGrid::from_str("#.\n.#\n").unwrap(), dense);
+        assert_eq!(
+            sparse,
+            SparseGrid::from_grid_at(origin, &dense, |c| *c == '#')
+        );
+
+        let moved = SparseGrid::from_grid_at(Coord::new(-5, 3), &grid, |c| *c == '#');
+        assert_eq!(Some('#'), moved.at_coord(Coord::new(-5, 4)));
+        assert_eq!(Coord::new(-5, 4), moved.to_grid('.').unwrap().0);
+
+        assert_eq!(None, SparseGrid::new().to_grid('.'));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{
+    adjacency, BoundingBox, Coord, CoordGrid, Grid, Neighbour, Neighbourhood, Offset, Pos,
+};
+
+/// What lies past the edges of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Topology {
+    /// Nothing: the grid is all there is.
+    #[default]
+    Bounded,
+    /// The grid itself: leaving it by an edge comes back in by the opposite edge, to the same
+    /// cells.
+    Torus,
+    /// Copies of the grid, repeated forever in every direction. The cells of the copies look like
+    /// the cells of the grid, but are distinct from them.
+    InfiniteTiling,
+}
+
+impl<T> Grid<T> {
+    /// The cell of the grid at `coord`, after wrapping it around the edges unless the grid is
+    /// [`Topology::Bounded`]. In an [`Topology::InfiniteTiling`], this is the cell of the grid
+    /// which the cell of the copy looks like.
+    pub fn wrap(&self, coord: Coord) -> Option<Pos> {
+        let (rows, cols) = (self.rows() as i64, self.cols() as i64);
+
+        if rows == 0 {
+            return None;
+        }
+
+        match self.topology() {
+            Topology::Bounded => ((0..rows).contains(&coord.row) && (0..cols).contains(&coord.col))
+                .then(|| Pos::new(coord.row as usize, coord.col as usize)),
+            Topology::Torus | Topology::InfiniteTiling => Some(Pos::new(
+                coord.row.rem_euclid(rows) as usize,
+                coord.col.rem_euclid(cols) as usize,
+            )),
+        }
+    }
+
+    /// Move `pos` by `offset`, coming back by the opposite edge when leaving a
+    /// [`Topology::Torus`].
+    ///
+    /// A [`Pos`] cannot designate the cells of the copies of an [`Topology::InfiniteTiling`], so
+    /// moves leaving it give `None` as in a [`Topology::Bounded`] grid: go past its edges with
+    /// [`Coord`] and [`Grid::adj_coords`] instead.
+    pub fn wrapping_add(&self, pos: Pos, offset: Offset) -> Option<Pos> {
+        match self.topology() {
+            Topology::Torus => self.wrap(Coord::from(pos) + offset),
+            Topology::Bounded | Topology::InfiniteTiling => self.checked_add(pos, offset),
+        }
+    }
+
+    /// Like [`Grid::adj_pos_rc`], but moving like [`Grid::wrapping_add`].
+    pub fn adj_pos_wrapping(&self, pos: Pos, neighbourhood: &Neighbourhood) -> Vec<Neighbour> {
+        adjacency::neighbours(pos, neighbourhood, |pos, offset| {
+            self.wrapping_add(pos, offset)
+        })
+    }
+
+    pub fn get_coord(&self, coord: Coord) -> Option<&T> {
+        self.wrap(coord).and_then(|pos| self.get_rc(pos))
+    }
+
+    /// The coordinates around `coord` in `neighbourhood`: inside the grid if it is
+    /// [`Topology::Bounded`], wrapped into it if it is a [`Topology::Torus`], and as is in an
+    /// [`Topology::InfiniteTiling`].
+    pub fn adj_coords(&self, coord: Coord, neighbourhood: &Neighbourhood) -> Vec<Coord> {
+        neighbourhood
+            .offsets()
+            .into_iter()
+            .map(|offset| coord + offset)
+            .filter_map(|neighbour| match self.topology() {
+                Topology::Bounded | Topology::Torus => self.wrap(neighbour).map(Coord::from),
+                Topology::InfiniteTiling => self.wrap(neighbour).map(|_| neighbour),
+            })
+            .collect()
+    }
+
+    /// The whole grid, unless it is empty.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        (!self.is_empty()).then(|| BoundingBox {
+            min: Pos::new(0, 0),
+            max: Pos::new(self.rows() - 1, self.cols() - 1),
+        })
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn at_coord(&self, coord: Coord) -> Option<T> {
+        self.get_coord(coord).copied()
+    }
+
+    /// The cells around `coord` in `neighbourhood`, as found by [`Grid::adj_coords`].
+    pub fn adj_cells_coord(&self, coord: Coord, neighbourhood: &Neighbourhood) -> Vec<(Coord, T)> {
+        CoordGrid::adj_cells_coord(self, coord, neighbourhood)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{CharGrid, Offset};
+
+    use super::*;
+
+    #[test]
+    fn bounded() {
+        let grid = CharGrid::from_str("abc\ndef\n").unwrap();
+
+        assert_eq!(Topology::Bounded, grid.topology());
+        assert_eq!(Some('f'), grid.at_coord(Coord::new(1, 2)));
+        assert_eq!(None, grid.at_coord(Coord::new(-1, 2)));
+        assert_eq!(None, grid.at_coord(Coord::new(1, 3)));
+        assert_eq!(
+            vec![Coord::new(0, 1), Coord::new(1, 0)],
+            grid.adj_coords(Coord::new(0, 0), &Neighbourhood::VonNeumann)
+        );
+    }
+
+    #[test]
+    fn torus() {
+        let grid = CharGrid::from_str("abc\ndef\n")
+            .unwrap()
+            .with_topology(Topology::Torus);
+
+        assert_eq!(Some('f'), grid.at_coord(Coord::new(-1, -1)));
+        assert_eq!(Some('a'), grid.at_coord(Coord::new(4, 6)));
+        assert_eq!(
+            vec![
+                Coord::new(1, 0),
+                Coord::new(0, 2),
+                Coord::new(0, 1),
+                Coord::new(1, 0)
+            ],
+            grid.adj_coords(Coord::new(0, 0), &Neighbourhood::VonNeumann)
+        );
+
+        let wrapped: Vec<Pos> = grid
+            .adj_pos_wrapping(Pos::new(0, 0), &Neighbourhood::VonNeumann)
+            .into_iter()
+            .map(|neighbour| neighbour.pos)
+            .collect();
+        assert_eq!(
+            vec![
+                Pos::new(1, 0),
+                Pos::new(0, 2),
+                Pos::new(0, 1),
+                Pos::new(1, 0)
+            ],
+            wrapped,
+            "neighbours wrap around the edges"
+        );
+        assert_eq!(
+            Some(Pos::new(1, 0)),
+            grid.wrapping_add(Pos::new(1, 2), Offset::new(0, 1))
+        );
+        assert_eq!(
+            None,
+            grid.checked_add(Pos::new(1, 2), Offset::new(0, 1)),
+            "bounds-checked whatever the topology"
+        );
+        assert_eq!(vec!['b', 'd', 'e'], grid.adj_vals(0, &Neighbourhood::Moore));
+
+        let region = CharGrid::from_str("#..#\n....\n")
+            .unwrap()
+            .with_topology(Topology::Torus)
+            .flood_fill(Pos::new(0, 0), &Neighbourhood::VonNeumann, |c| *c == '#')
+            .unwrap();
+        assert_eq!((2, 6), (region.area(), region.perimeter()));
+        assert_eq!(Topology::Torus, grid.rotate_cw().topology());
+    }
+
+    #[test]
+    fn infinite_tiling() {
+        let grid = CharGrid::from_str("abc\ndef\n")
+            .unwrap()
+            .with_topology(Topology::InfiniteTiling);
+
+        assert_eq!(Some('e'), grid.at_coord(Coord::new(-3, 7)));
+        assert_eq!(None, grid.checked_add(Pos::new(0, 0), Offset::new(0, -1)));
+        assert_eq!(
+            None,
+            grid.wrapping_add(Pos::new(0, 0), Offset::new(0, -1)),
+            "positions cannot leave the grid for a copy"
+        );
+        assert_eq!(
+            vec![
+                Coord::new(-1, 0),
+                Coord::new(0, -1),
+                Coord::new(0, 1),
+                Coord::new(1, 0)
+            ],
+            grid.adj_coords(Coord::new(0, 0), &Neighbourhood::VonNeumann)
+        );
+
+        let reachable = crate::search::bfs(
+            Coord::new(0, 0),
+            |coord| grid.adj_coords(*coord, &Neighbourhood::VonNeumann),
+            |coord| *coord == Coord::new(10, -10),
+        );
+        assert_eq!(Some(20), reachable.distance(&Coord::new(10, -10)));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{Grid, Pos};
+
+impl<T: Clone> Grid<T> {
+    /// Build a `rows` by `cols` grid whose cell at each position is the cell of `self` at
+    /// `source(pos)`.
+    fn rearrange(&self, rows: usize, cols: usize, source: impl Fn(Pos) -> Pos) -> Self {
+        if self.is_empty() {
+            return self.clone();
+        }
+
+        let cells = (0..rows * cols)
+            .map(|index| {
+                self.get_rc(source(Pos::from_index(index, cols)))
+                    .unwrap()
+                    .clone()
+            })
+            .collect();
+
+        Grid::new(cols, cells).with_topology(self.topology())
+    }
+
+    /// Swap rows and columns: the first row becomes the first column.
+    pub fn transpose(&self) -> Self {
+        self.rearrange(self.cols(), self.rows(), |pos| Pos::new(pos.col, pos.row))
+    }
+
+    /// Rotate by 90 degrees clockwise: the first column becomes the first row, reversed.
+    pub fn rotate_cw(&self) -> Self {
+        let rows = self.rows();
+
+        self.rearrange(self.cols(), rows, |pos| {
+            Pos::new(rows - 1 - pos.col, pos.row)
+        })
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        let (rows, cols) = (self.rows(), self.cols());
+
+        self.rearrange(rows, cols, |pos| {
+            Pos::new(rows - 1 - pos.row, cols - 1 - pos.col)
+        })
+    }
+
+    /// Rotate by 90 degrees counter-clockwise (or 270 degrees clockwise): the last column becomes
+    /// the first row.
+    pub fn rotate_ccw(&self) -> Self {
+        let cols = self.cols();
+
+        self.rearrange(cols, self.rows(), |pos| {
+            Pos::new(pos.col, cols - 1 - pos.row)
+        })
+    }
+
+    /// Mirror left to right: every row is reversed.
+    pub fn flip_horizontal(&self) -> Self {
+        let cols = self.cols();
+
+        self.rearrange(self.rows(), cols, |pos| {
+            Pos::new(pos.row, cols - 1 - pos.col)
+        })
+    }
+
+    /// Mirror top to bottom: the rows come in reverse order.
+    pub fn flip_vertical(&self) -> Self {
+        let rows = self.rows();
+
+        self.rearrange(rows, self.cols(), |pos| {
+            Pos::new(rows - 1 - pos.row, pos.col)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::CharGrid;
+
+    fn grid(s: &str) -> CharGrid {
+        CharGrid::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn transformations() {
+        let abc = grid("abc\ndef\n");
+
+        assert_eq!(grid("ad\nbe\ncf\n"), abc.transpose());
+        assert_eq!(grid("da\neb\nfc\n"), abc.rotate_cw());
+        assert_eq!(grid("fed\ncba\n"), abc.rotate_180());
+        assert_eq!(grid("cf\nbe\nad\n"), abc.rotate_ccw());
+        assert_eq!(grid("cba\nfed\n"), abc.flip_horizontal());
+        assert_eq!(grid("def\nabc\n"), abc.flip_vertical());
+
+        assert_eq!(abc, abc.transpose().transpose());
+        assert_eq!(abc.rotate_180(), abc.rotate_cw().rotate_cw());
+        assert_eq!(abc.rotate_ccw(), abc.rotate_cw().rotate_180());
+        assert_eq!(abc, abc.rotate_cw().rotate_ccw());
+
+        let empty = CharGrid::new(3, Vec::new());
+        assert_eq!(empty, empty.rotate_cw());
+    }
+}
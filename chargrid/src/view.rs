@@ -0,0 +1,203 @@
+use crate::{adjacency::Cells, Grid, Neighbour, Neighbourhood, Offset, Pos};
+
+/// A rectangular part of a grid, borrowed rather than copied.
+///
+/// Positions are relative to the top left corner of the view, and cells outside of it are not
+/// visible, not even as neighbours.
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Pos,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// View the `rows` by `cols` cells whose top left corner is `origin`, unless there are none or
+    /// they do not all fit in the grid.
+    pub fn view(&self, origin: Pos, rows: usize, cols: usize) -> Option<GridView<'_, T>> {
+        GridView {
+            grid: self,
+            origin: Pos::new(0, 0),
+            rows: self.rows(),
+            cols: self.cols(),
+        }
+        .view(origin, rows, cols)
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    /// View a part of this view, with `origin` relative to it, unless it is empty or does not
+    /// fit in this view.
+    pub fn view(&self, origin: Pos, rows: usize, cols: usize) -> Option<GridView<'a, T>> {
+        let fits = origin.row + rows <= self.rows && origin.col + cols <= self.cols;
+        let fits = fits && rows > 0 && cols > 0;
+
+        fits.then(|| GridView {
+            grid: self.grid,
+            origin: Pos::new(self.origin.row + origin.row, self.origin.col + origin.col),
+            rows,
+            cols,
+        })
+    }
+
+    /// The position of the top left corner of the view in the underlying grid.
+    pub fn origin(&self) -> Pos {
+        self.origin
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows * self.cols
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, pos: usize) -> Option<&'a T> {
+        if pos >= self.len() {
+            return None;
+        }
+
+        self.get_rc(self.pos(pos))
+    }
+
+    /// The position of the cell at the flat index `pos`.
+    pub fn pos(&self, pos: usize) -> Pos {
+        Pos::from_index(pos, self.cols)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.rows && pos.col < self.cols
+    }
+
+    pub fn get_rc(&self, pos: Pos) -> Option<&'a T> {
+        if !self.contains(pos) {
+            return None;
+        }
+
+        self.grid.get_rc(Pos::new(
+            self.origin.row + pos.row,
+            self.origin.col + pos.col,
+        ))
+    }
+
+    /// Move `pos` by `offset`, unless it would leave the view.
+    pub fn checked_add(&self, pos: Pos, offset: Offset) -> Option<Pos> {
+        pos.checked_add(offset).filter(|pos| self.contains(*pos))
+    }
+
+    pub fn adj_pos(&self, pos: usize, neighbourhood: &Neighbourhood) -> Vec<usize> {
+        self.neighbour_indices(pos, neighbourhood)
+    }
+
+    pub fn adj_pos_rc(&self, pos: Pos, neighbourhood: &Neighbourhood) -> Vec<Neighbour> {
+        self.neighbours(pos, neighbourhood)
+    }
+
+    /// The cells of the view, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = &'a T> + '_ {
+        (0..self.len()).filter_map(|pos| self.get(pos))
+    }
+}
+
+impl<T: Copy> GridView<'_, T> {
+    pub fn at(&self, pos: usize) -> Option<T> {
+        self.get(pos).copied()
+    }
+
+    pub fn at_rc(&self, row: usize, col: usize) -> Option<T> {
+        self.get_rc(Pos::new(row, col)).copied()
+    }
+
+    pub fn adj_vals(&self, pos: usize, neighbourhood: &Neighbourhood) -> Vec<T> {
+        self.adj_cells(pos, neighbourhood)
+            .into_iter()
+            .map(|(_index, cell)| cell)
+            .collect()
+    }
+
+    pub fn adj_cells(&self, pos: usize, neighbourhood: &Neighbourhood) -> Vec<(usize, T)> {
+        self.neighbour_cells(pos, neighbourhood)
+    }
+
+    pub fn adj_cells_rc(&self, pos: Pos, neighbourhood: &Neighbourhood) -> Vec<(Neighbour, T)> {
+        self.neighbour_cells_rc(pos, neighbourhood)
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Copy the cells of the view into a grid of their own.
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::new(self.cols, self.cells().cloned().collect())
+    }
+}
+
+impl<T> Cells<T> for GridView<'_, T> {
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn cell(&self, pos: Pos) -> Option<&T> {
+        self.get_rc(pos)
+    }
+
+    fn step(&self, pos: Pos, offset: Offset) -> Option<Pos> {
+        self.checked_add(pos, offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::CharGrid;
+
+    use super::*;
+
+    #[test]
+    fn views() {
+        let grid = CharGrid::from_str("abcd\nefgh\nijkl\n").unwrap();
+
+        let view = grid.view(Pos::new(1, 1), 2, 3).unwrap();
+        assert_eq!((2, 3, 6), (view.rows(), view.cols(), view.len()));
+        assert_eq!(Some('f'), view.at(0));
+        assert_eq!(Some('l'), view.at_rc(1, 2));
+        assert_eq!(None, view.at_rc(0, 3), "e is outside of the view");
+        assert_eq!(None, view.at(6));
+        assert_eq!("fghjkl", view.cells().collect::<String>());
+
+        assert_eq!(
+            vec!['g', 'j', 'k'],
+            view.adj_vals(0, &Neighbourhood::Moore),
+            "neighbours stay inside the view"
+        );
+        assert_eq!(
+            vec![(Pos::new(0, 1), 'g'), (Pos::new(1, 0), 'j')],
+            view.adj_cells_rc(Pos::new(0, 0), &Neighbourhood::VonNeumann)
+                .into_iter()
+                .map(|(neighbour, val)| (neighbour.pos, val))
+                .collect::<Vec<_>>()
+        );
+
+        let nested = view.view(Pos::new(1, 1), 1, 2).unwrap();
+        assert_eq!(Pos::new(2, 2), nested.origin());
+        assert_eq!(CharGrid::from_str("kl\n").unwrap(), nested.to_grid());
+
+        assert!(grid.view(Pos::new(1, 1), 3, 1).is_none(), "too many rows");
+        assert!(
+            view.view(Pos::new(0, 1), 1, 3).is_none(),
+            "too many columns"
+        );
+        assert!(grid.view(Pos::new(3, 0), 0, 4).is_none(), "no rows");
+        assert!(grid.view(Pos::new(0, 0), 2, 0).is_none(), "no columns");
+    }
+}
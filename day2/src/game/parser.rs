@@ -72,7 +72,7 @@ impl FromStr for Colour {
 fn parse_u64(input: &str) -> IResult<&str, u64> {
     let (remaining, digits) = digit1(input)?;
 
-    let num = u64::from_str_radix(digits, 10).unwrap();
+    let num = digits.parse().unwrap();
 
     Ok((remaining, num))
 }
@@ -0,0 +1,140 @@
+use std::fmt::Write;
+
+use crate::{
+    module::{ModuleKind, ModuleName, ModuleState, Pulse},
+    ModuleSystem,
+};
+
+impl ModuleSystem {
+    /// Describe the modules and their connections as a Graphviz DOT graph, for instance to render
+    /// it with `dot -Tsvg`.
+    ///
+    /// With `show_state`, the flip-flops which are on and the conjunctions which only remember
+    /// high pulses are filled, and conjunctions list what they remember of each input, in the
+    /// order of the input names.
+    pub fn to_dot(&self, show_state: bool) -> String {
+        let mut names: Vec<&ModuleName> = self.modules.keys().collect();
+        names.sort();
+
+        // Destinations without a module, such as `rx`, only receive pulses.
+        let mut sinks: Vec<&ModuleName> = self
+            .modules
+            .values()
+            .flat_map(|module| module.outputs())
+            .filter(|output| !self.modules.contains_key(*output))
+            .collect();
+        sinks.sort();
+        sinks.dedup();
+
+        let mut dot = String::from("digraph modules {\n");
+
+        for name in &names {
+            let module = &self.modules[*name];
+            let kind = module.kind();
+
+            let mut label = format!("{}{name}", kind.prefix());
+            let mut filled = false;
+
+            if show_state {
+                match module.state() {
+                    ModuleState::FlipFlop { on } => filled = on,
+                    ModuleState::Conjunction(remembered) => {
+                        label.push_str("\\n");
+                        label.extend(remembered.iter().map(|pulse| match pulse {
+                            Pulse::Low => 'L',
+                            Pulse::High => 'H',
+                        }));
+                        filled = remembered.iter().all(|pulse| *pulse == Pulse::High);
+                    }
+                    ModuleState::Stateless => {}
+                }
+            }
+
+            write!(
+                dot,
+                "    \"{name}\" [shape={}, label=\"{label}\"",
+                shape(kind)
+            )
+            .unwrap();
+
+            if filled {
+                write!(dot, ", style=filled, fillcolor={}", fill_colour(kind)).unwrap();
+            }
+
+            dot.push_str("];\n");
+        }
+
+        for sink in &sinks {
+            writeln!(dot, "    \"{sink}\" [shape={}];", shape(ModuleKind::Output)).unwrap();
+        }
+
+        for name in &names {
+            for output in self.modules[*name].outputs() {
+                writeln!(dot, "    \"{name}\" -> \"{output}\";").unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+fn shape(kind: ModuleKind) -> &'static str {
+    match kind {
+        ModuleKind::Broadcaster => "doubleoctagon",
+        ModuleKind::FlipFlop => "box",
+        ModuleKind::Conjunction => "invtrapezium",
+        ModuleKind::Output => "ellipse",
+    }
+}
+
+fn fill_colour(kind: ModuleKind) -> &'static str {
+    match kind {
+        ModuleKind::Conjunction => "gold",
+        _ => "palegreen",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, samples::SAMPLE_2};
+
+    #[test]
+    fn test_to_dot() {
+        let mut system = parse(SAMPLE_2).unwrap();
+
+        assert_eq!(
+            "\
+digraph modules {
+    \"a\" [shape=box, label=\"%a\"];
+    \"b\" [shape=box, label=\"%b\"];
+    \"broadcaster\" [shape=doubleoctagon, label=\"broadcaster\"];
+    \"con\" [shape=invtrapezium, label=\"&con\"];
+    \"inv\" [shape=invtrapezium, label=\"&inv\"];
+    \"output\" [shape=ellipse];
+    \"a\" -> \"inv\";
+    \"a\" -> \"con\";
+    \"b\" -> \"con\";
+    \"broadcaster\" -> \"a\";
+    \"con\" -> \"output\";
+    \"inv\" -> \"b\";
+}
+",
+            system.to_dot(false)
+        );
+
+        system.run().unwrap();
+
+        let dot = system.to_dot(true);
+
+        for node in [
+            "\"a\" [shape=box, label=\"%a\", style=filled, fillcolor=palegreen];",
+            "\"b\" [shape=box, label=\"%b\", style=filled, fillcolor=palegreen];",
+            "\"con\" [shape=invtrapezium, label=\"&con\\nHH\", style=filled, fillcolor=gold];",
+            "\"inv\" [shape=invtrapezium, label=\"&inv\\nH\", style=filled, fillcolor=gold];",
+        ] {
+            assert!(dot.contains(node), "{node}");
+        }
+    }
+}
@@ -1,11 +1,26 @@
-mod module;
+mod dot;
+pub mod module;
+pub mod observer;
 mod parser;
+#[cfg(test)]
+mod samples;
+mod solution;
+pub mod trace;
+mod validate;
 
-pub use parser::parse;
+pub use parser::{parse, parse_strict, ParseError, ParseErrorKind};
+pub use solution::Day20;
+pub use validate::ValidationWarning;
 
-use std::collections::{HashMap, VecDeque};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    ops::ControlFlow,
+};
 
-use module::{Module, ModuleName, ModuleType, Pulse};
+use aocutil::math;
+use module::{Module, ModuleKind, ModuleName, ModuleState, ModuleType, Pulse};
+use observer::{FirstHighPulses, PulseObserver};
+use trace::Trace;
 
 #[derive(Debug, PartialEq)]
 pub struct SendPulseRequest {
@@ -18,11 +33,132 @@ type ModuleRegistry = HashMap<ModuleName, Module<Box<dyn ModuleType>>>;
 
 pub struct ModuleSystem {
     modules: ModuleRegistry,
+    /// The names which receive pulses without being modules, such as `rx`.
+    sinks: HashSet<ModuleName>,
     stats: ModuleSystemStats,
+    /// The number of times the button was pressed.
+    presses: u64,
+    trace: Option<Trace>,
+    observers: Vec<Box<dyn PulseObserver>>,
 }
 
 impl ModuleSystem {
+    /// A snapshot of what every module remembers, which decides what the next presses do.
+    pub fn state(&self) -> ModuleSystemState {
+        let mut modules: Vec<(ModuleName, ModuleState)> = self
+            .modules
+            .iter()
+            .map(|(name, module)| (name.clone(), module.state()))
+            .collect();
+
+        modules.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        ModuleSystemState(modules)
+    }
+
+    /// Start keeping every pulse delivered from now on, discarding any previous trace.
+    pub fn record_trace(&mut self) {
+        self.trace = Some(Trace::default());
+    }
+
+    /// The pulses delivered since [`record_trace`](Self::record_trace) was called.
+    pub fn trace(&self) -> Option<&Trace> {
+        self.trace.as_ref()
+    }
+
+    /// Stop recording, and return what was recorded.
+    pub fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+
+    /// Press the button once, and return the counts of pulses since the system was built.
+    ///
+    /// The press stops early if a registered observer asks for it.
     pub fn run(&mut self) -> Result<ModuleSystemStats, ModuleNotFoundError> {
+        let _stopped = self.press(None)?;
+
+        Ok(self.stats)
+    }
+
+    /// Press the button once, telling `observer` about the pulses on top of the registered
+    /// observers, and return whether one of them stopped the press.
+    pub fn press_with(
+        &mut self,
+        observer: &mut impl PulseObserver,
+    ) -> Result<ControlFlow<()>, ModuleNotFoundError> {
+        self.press(Some(observer))
+    }
+
+    /// Tell `observer` about every pulse delivered by the next presses.
+    pub fn add_observer(&mut self, observer: impl PulseObserver + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+
+    /// Count the button presses needed for `target` to receive a low pulse.
+    ///
+    /// Pressing the button until it happens never finishes on real inputs, so this relies on
+    /// their shape: `target` is fed by a single conjunction, and each input of that conjunction
+    /// sends it a high pulse periodically. The answer is the LCM of these periods. Other shapes
+    /// are an error.
+    ///
+    /// The periods are only known from the first press, so the button must not have been pressed
+    /// yet.
+    ///
+    /// This never returns if one of the conjunction inputs never sends a high pulse.
+    pub fn presses_until_low_pulse(&mut self, target: &str) -> Result<u64, LowPulseError> {
+        if self.presses != 0 {
+            return Err(LowPulseError::AlreadyPressed(self.presses));
+        }
+
+        let mut feeders: Vec<(&ModuleName, &Module<Box<dyn ModuleType>>)> = self
+            .modules
+            .iter()
+            .filter(|(_name, module)| module.outputs().iter().any(|output| output == target))
+            .collect();
+
+        feeders.sort_by_key(|(name, _module)| *name);
+
+        let (feeder, feeder_module) = match feeders[..] {
+            [] => return Err(LowPulseError::NoFeeder(ModuleName::from(target))),
+            [feeder] => feeder,
+            _ => {
+                return Err(LowPulseError::SeveralFeeders(
+                    feeders.into_iter().map(|(name, _)| name.clone()).collect(),
+                ))
+            }
+        };
+
+        if feeder_module.kind() != ModuleKind::Conjunction {
+            return Err(LowPulseError::FeederNotConjunction(feeder.clone()));
+        }
+
+        if feeder_module.inputs().is_empty() {
+            return Err(LowPulseError::FeederWithoutInputs(feeder.clone()));
+        }
+
+        let feeder = feeder.clone();
+        let mut watcher = FirstHighPulses::new(feeder_module.inputs().to_vec(), feeder.clone());
+
+        while !watcher.is_complete() {
+            if self.press(Some(&mut watcher))?.is_break() {
+                return Err(LowPulseError::Stopped(self.presses));
+            }
+        }
+
+        // Counting from the first press, the first high pulse of each input comes after a period.
+        let periods = watcher.first_presses();
+
+        log::debug!("Periods of the inputs of {feeder}: {periods:?}");
+
+        math::lcm_all(periods.values().copied()).ok_or(LowPulseError::Overflow)
+    }
+
+    fn press(
+        &mut self,
+        mut observer: Option<&mut dyn PulseObserver>,
+    ) -> Result<ControlFlow<()>, ModuleNotFoundError> {
+        self.presses += 1;
+
         let initial_request = SendPulseRequest {
             origin: ModuleName::from("button"),
             pulse: Pulse::Low,
@@ -32,27 +168,32 @@ impl ModuleSystem {
         let mut pending = VecDeque::from([initial_request]);
 
         while let Some(request) = pending.pop_front() {
-            pending.extend(self.handle(request)?.into_iter());
+            if self
+                .handle(request, reborrow(&mut observer), &mut pending)?
+                .is_break()
+            {
+                return Ok(ControlFlow::Break(()));
+            }
         }
 
-        Ok(self.stats)
+        Ok(ControlFlow::Continue(()))
     }
 
     fn handle(
         &mut self,
         request: SendPulseRequest,
-    ) -> Result<Vec<SendPulseRequest>, ModuleNotFoundError> {
+        mut observer: Option<&mut dyn PulseObserver>,
+        pending: &mut VecDeque<SendPulseRequest>,
+    ) -> Result<ControlFlow<()>, ModuleNotFoundError> {
         log::trace!("Handling {request:?}");
-        let mut reactions = Vec::new();
 
         for destination in request.destinations.iter() {
-            let dest_module = self.modules.get_mut(destination);
-            log::trace!("Destination module lookup for {destination:?}: {dest_module:?}");
-
-            match request.pulse {
-                Pulse::Low => self.stats.low_pulse_count += 1,
-                Pulse::High => self.stats.high_pulse_count += 1,
-            };
+            let flow = self.notify(
+                reborrow(&mut observer),
+                &request.origin,
+                destination,
+                request.pulse,
+            );
 
             log::debug!(
                 "Sending pulse {} → {:?} → {} [{:?}]",
@@ -62,41 +203,123 @@ impl ModuleSystem {
                 self.stats
             );
 
-            if dest_module.is_none() {
-                continue;
+            let dest_module = self.modules.get_mut(destination);
+            log::trace!("Destination module lookup for {destination:?}: {dest_module:?}");
+
+            match dest_module {
+                Some(dest_module) => {
+                    if let Some(reaction) = dest_module.send_pulse(request.pulse, &request.origin) {
+                        log::trace!("Reaction is: {reaction:?}");
+                        pending.push_back(reaction);
+                    } else {
+                        log::trace!("No reaction");
+                    }
+                }
+                None if self.sinks.contains(destination) => {}
+                None => return Err(ModuleNotFoundError(destination.clone())),
             }
 
-            let dest_module = dest_module.unwrap();
+            if flow.is_break() {
+                log::debug!("Press {} stopped by an observer", self.presses);
+                return Ok(flow);
+            }
+        }
 
-            if let Some(reaction) = dest_module.send_pulse(request.pulse, &request.origin) {
-                log::trace!("Reaction is: {reaction:?}");
-                reactions.push(reaction);
-            } else {
-                log::trace!("No reaction");
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// Tell every observer about a pulse, even when one of the first ones asks to stop.
+    fn notify(
+        &mut self,
+        observer: Option<&mut dyn PulseObserver>,
+        origin: &ModuleName,
+        destination: &ModuleName,
+        pulse: Pulse,
+    ) -> ControlFlow<()> {
+        let observers = [
+            Some(&mut self.stats as &mut dyn PulseObserver),
+            self.trace
+                .as_mut()
+                .map(|trace| trace as &mut dyn PulseObserver),
+            observer,
+        ];
+
+        let mut flow = ControlFlow::Continue(());
+
+        for observer in observers.into_iter().flatten().chain(
+            self.observers
+                .iter_mut()
+                .map(|observer| &mut **observer as &mut dyn PulseObserver),
+        ) {
+            if observer
+                .observe(self.presses, origin, destination, pulse)
+                .is_break()
+            {
+                flow = ControlFlow::Break(());
             }
         }
 
-        Ok(reactions)
+        flow
     }
 }
 
+/// Lend the observer of a press to the handling of one of its pulses.
+fn reborrow<'a>(
+    observer: &'a mut Option<&mut dyn PulseObserver>,
+) -> Option<&'a mut dyn PulseObserver> {
+    observer
+        .as_mut()
+        .map(|observer| &mut **observer as &mut dyn PulseObserver)
+}
+
 impl From<ModuleRegistry> for ModuleSystem {
     fn from(value: ModuleRegistry) -> Self {
         Self {
             modules: value,
+            sinks: HashSet::new(),
             stats: ModuleSystemStats::default(),
+            presses: 0,
+            trace: None,
+            observers: Vec::new(),
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModuleSystemState(Vec<(ModuleName, ModuleState)>);
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct ModuleSystemStats {
     pub low_pulse_count: usize,
     pub high_pulse_count: usize,
 }
 
-#[derive(Debug)]
+impl PulseObserver for ModuleSystemStats {
+    fn observe(
+        &mut self,
+        _press: u64,
+        _origin: &ModuleName,
+        _destination: &ModuleName,
+        pulse: Pulse,
+    ) -> ControlFlow<()> {
+        match pulse {
+            Pulse::Low => self.low_pulse_count += 1,
+            Pulse::High => self.high_pulse_count += 1,
+        };
+
+        ControlFlow::Continue(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ModuleNotFoundError(ModuleName);
+
+impl ModuleNotFoundError {
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
 impl std::error::Error for ModuleNotFoundError {}
 impl std::fmt::Display for ModuleNotFoundError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -104,9 +327,62 @@ impl std::fmt::Display for ModuleNotFoundError {
     }
 }
 
+/// Why [`ModuleSystem::presses_until_low_pulse`] cannot count the presses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LowPulseError {
+    ModuleNotFound(ModuleNotFoundError),
+    /// No module sends pulses to the target.
+    NoFeeder(ModuleName),
+    /// More than one module sends pulses to the target, listed in the order of their names.
+    SeveralFeeders(Vec<ModuleName>),
+    /// The only module sending pulses to the target is not a conjunction.
+    FeederNotConjunction(ModuleName),
+    /// The conjunction feeding the target never receives a pulse, so it never sends one.
+    FeederWithoutInputs(ModuleName),
+    /// A registered observer stopped the press with this number, so the periods are unknown.
+    Stopped(u64),
+    /// The button was already pressed this many times, so the periods cannot be measured.
+    AlreadyPressed(u64),
+    /// The number of presses does not fit in a `u64`.
+    Overflow,
+}
+
+impl From<ModuleNotFoundError> for LowPulseError {
+    fn from(value: ModuleNotFoundError) -> Self {
+        Self::ModuleNotFound(value)
+    }
+}
+
+impl std::error::Error for LowPulseError {}
+impl std::fmt::Display for LowPulseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LowPulseError::ModuleNotFound(err) => write!(f, "{err}"),
+            LowPulseError::NoFeeder(target) => write!(f, "no module sends pulses to {target}"),
+            LowPulseError::SeveralFeeders(feeders) => {
+                write!(f, "several modules feed the target: {}", feeders.join(", "))
+            }
+            LowPulseError::FeederNotConjunction(feeder) => {
+                write!(f, "module {feeder} is not a conjunction")
+            }
+            LowPulseError::FeederWithoutInputs(feeder) => {
+                write!(f, "conjunction {feeder} has no inputs")
+            }
+            LowPulseError::Stopped(press) => write!(f, "press {press} stopped by an observer"),
+            LowPulseError::AlreadyPressed(presses) => {
+                write!(f, "the button was already pressed {presses} times")
+            }
+            LowPulseError::Overflow => write!(f, "the number of presses overflows"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::module::{Broadcaster, Conjunction, FlipFlop, Output};
+    use crate::{
+        module::{Broadcaster, Conjunction, FlipFlop, Output},
+        samples::SAMPLE_3,
+    };
 
     use super::*;
 
@@ -258,4 +534,104 @@ mod tests {
         assert_eq!(stats.low_pulse_count, 4250);
         assert_eq!(stats.high_pulse_count, 2750);
     }
+
+    #[test]
+    fn test_presses_until_low_pulse() {
+        let mut system = crate::parse(SAMPLE_3).unwrap();
+        assert_eq!(system.presses_until_low_pulse("rx").unwrap(), 4);
+
+        let mut system = crate::parse(SAMPLE_3).unwrap();
+        let mut presses = 0;
+        let mut rx_got_low = |press, _origin: &ModuleName, destination: &ModuleName, pulse| {
+            presses = press;
+
+            if pulse == Pulse::Low && destination == "rx" {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+
+        while system.press_with(&mut rx_got_low).unwrap().is_continue() {}
+
+        assert_eq!(presses, 4, "brute force agrees");
+
+        let mut system = crate::parse(SAMPLE_3).unwrap();
+        assert_eq!(
+            Err(LowPulseError::NoFeeder(ModuleName::from("nope"))),
+            system.presses_until_low_pulse("nope")
+        );
+    }
+
+    #[test]
+    fn test_presses_until_low_pulse_stopped() {
+        let mut system = crate::parse(SAMPLE_3).unwrap();
+        system.add_observer(
+            |press, _origin: &ModuleName, _destination: &ModuleName, _pulse| {
+                if press == 1 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+        );
+
+        assert_eq!(
+            Err(LowPulseError::Stopped(1)),
+            system.presses_until_low_pulse("rx")
+        );
+    }
+
+    #[test]
+    fn test_presses_until_low_pulse_already_pressed() {
+        let mut system = crate::parse(SAMPLE_3).unwrap();
+        system.run().unwrap();
+
+        assert_eq!(
+            Err(LowPulseError::AlreadyPressed(1)),
+            system.presses_until_low_pulse("rx")
+        );
+    }
+
+    #[test]
+    fn test_presses_until_low_pulse_after_run() {
+        let mut system = crate::parse(SAMPLE_3).unwrap();
+        assert_eq!(Ok(4), system.presses_until_low_pulse("rx"));
+
+        // The last press was not cut short, so the system is where 4 full presses leave it.
+        let mut fresh = crate::parse(SAMPLE_3).unwrap();
+        for _ in 0..4 {
+            fresh.run().unwrap();
+        }
+
+        assert_eq!(fresh.state(), system.state());
+        assert_eq!(
+            fresh.run().unwrap().low_pulse_count,
+            system.run().unwrap().low_pulse_count
+        );
+    }
+
+    #[test]
+    fn test_presses_until_low_pulse_unsupported_shapes() {
+        let mut system = crate::parse("broadcaster -> a\n%a -> rx\n").unwrap();
+        assert_eq!(
+            Err(LowPulseError::FeederNotConjunction(ModuleName::from("a"))),
+            system.presses_until_low_pulse("rx")
+        );
+
+        let mut system = crate::parse("broadcaster -> a\n&hub -> rx\n%a -> b\n").unwrap();
+        assert_eq!(
+            Err(LowPulseError::FeederWithoutInputs(ModuleName::from("hub"))),
+            system.presses_until_low_pulse("rx")
+        );
+
+        let mut system = crate::parse("broadcaster -> b, a\n&b -> rx\n&a -> rx\n").unwrap();
+        assert_eq!(
+            Err(LowPulseError::SeveralFeeders(vec![
+                ModuleName::from("a"),
+                ModuleName::from("b")
+            ])),
+            system.presses_until_low_pulse("rx")
+        );
+    }
 }
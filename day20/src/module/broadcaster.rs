@@ -1,6 +1,6 @@
-use super::{ModuleName, ModuleType, Pulse};
+use super::{ModuleKind, ModuleName, ModuleType, Pulse};
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Broadcaster;
 
 impl Broadcaster {
@@ -10,6 +10,10 @@ impl Broadcaster {
 }
 
 impl ModuleType for Broadcaster {
+    fn kind(&self) -> ModuleKind {
+        ModuleKind::Broadcaster
+    }
+
     fn handle_pulse(&mut self, pulse: Pulse, _from: &ModuleName) -> Option<Pulse> {
         Some(pulse)
     }
@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
-use super::{ModuleName, ModuleType, Pulse};
+use super::{ModuleKind, ModuleName, ModuleState, ModuleType, Pulse};
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Conjunction {
     last_received: HashMap<ModuleName, Pulse>,
 }
@@ -20,6 +20,10 @@ fn init_input(input: &ModuleName) -> (ModuleName, Pulse) {
 }
 
 impl ModuleType for Conjunction {
+    fn kind(&self) -> ModuleKind {
+        ModuleKind::Conjunction
+    }
+
     fn connect(&mut self, inputs: &Vec<ModuleName>) {
         self.last_received.extend(inputs.iter().map(init_input));
     }
@@ -31,6 +35,18 @@ impl ModuleType for Conjunction {
 
         Some(if all_high { Pulse::Low } else { Pulse::High })
     }
+
+    fn state(&self) -> ModuleState {
+        let mut inputs: Vec<&ModuleName> = self.last_received.keys().collect();
+        inputs.sort();
+
+        ModuleState::Conjunction(
+            inputs
+                .into_iter()
+                .map(|input| self.last_received[input])
+                .collect(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -55,5 +71,11 @@ mod tests {
 
         assert_eq!(sut.handle_pulse(Pulse::Low, &sender1), Some(Pulse::High));
         assert_eq!(sut.handle_pulse(Pulse::High, &sender1), Some(Pulse::Low));
+
+        sut.handle_pulse(Pulse::Low, &sender2);
+        assert_eq!(
+            sut.state(),
+            ModuleState::Conjunction(vec![Pulse::High, Pulse::Low])
+        );
     }
 }
@@ -1,8 +1,9 @@
-use super::{ModuleName, ModuleType, Pulse};
+use super::{ModuleKind, ModuleName, ModuleState, ModuleType, Pulse};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default, PartialEq)]
 pub enum FlipFlop {
     On,
+    #[default]
     Off,
 }
 
@@ -13,6 +14,14 @@ impl FlipFlop {
         Off
     }
 
+    /// The pulse sent when switching to the current state.
+    fn last_sent(&self) -> Pulse {
+        match self {
+            On => Pulse::High,
+            Off => Pulse::Low,
+        }
+    }
+
     fn switch(&mut self) {
         match self {
             On => *self = Off,
@@ -22,19 +31,24 @@ impl FlipFlop {
 }
 
 impl ModuleType for FlipFlop {
+    fn kind(&self) -> ModuleKind {
+        ModuleKind::FlipFlop
+    }
+
     fn handle_pulse(&mut self, pulse: Pulse, _from: &ModuleName) -> Option<Pulse> {
         match pulse {
             Pulse::High => None,
             Pulse::Low => {
                 self.switch();
 
-                Some(match self {
-                    On => Pulse::High,
-                    Off => Pulse::Low,
-                })
+                Some(self.last_sent())
             }
         }
     }
+
+    fn state(&self) -> ModuleState {
+        ModuleState::FlipFlop { on: *self == On }
+    }
 }
 
 #[cfg(test)]
@@ -64,5 +78,6 @@ mod tests {
 
         assert_eq!(sut.handle_pulse(Pulse::Low, &sender), Some(Pulse::Low));
         assert_eq!(sut, Off);
+        assert_eq!(sut.state(), ModuleState::FlipFlop { on: false });
     }
 }
@@ -22,12 +22,42 @@ pub struct Module<T> {
     module_type: T,
 }
 
+/// The types of modules, as written in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleKind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    Output,
+}
+
+impl ModuleKind {
+    /// The prefix of the names of the modules of this kind in the input.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            ModuleKind::FlipFlop => "%",
+            ModuleKind::Conjunction => "&",
+            ModuleKind::Broadcaster | ModuleKind::Output => "",
+        }
+    }
+}
+
 pub trait ModuleType: Debug {
+    fn kind(&self) -> ModuleKind;
     fn connect(&mut self, _inputs: &Vec<ModuleName>) {}
     fn handle_pulse(&mut self, pulse: Pulse, from: &ModuleName) -> Option<Pulse>;
+
+    /// What the module remembers, which decides how it handles the next pulses.
+    fn state(&self) -> ModuleState {
+        ModuleState::Stateless
+    }
 }
 
 impl<M: ModuleType + ?Sized> ModuleType for Box<M> {
+    fn kind(&self) -> ModuleKind {
+        (**self).kind()
+    }
+
     fn connect(&mut self, inputs: &Vec<ModuleName>) {
         (**self).connect(inputs)
     }
@@ -35,6 +65,10 @@ impl<M: ModuleType + ?Sized> ModuleType for Box<M> {
     fn handle_pulse(&mut self, pulse: Pulse, from: &ModuleName) -> Option<Pulse> {
         (**self).handle_pulse(pulse, from)
     }
+
+    fn state(&self) -> ModuleState {
+        (**self).state()
+    }
 }
 
 impl<T: ModuleType> Module<T> {
@@ -56,6 +90,22 @@ impl<T: ModuleType> Module<T> {
         module
     }
 
+    pub fn kind(&self) -> ModuleKind {
+        self.module_type.kind()
+    }
+
+    pub fn inputs(&self) -> &[ModuleName] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[ModuleName] {
+        &self.outputs
+    }
+
+    pub fn state(&self) -> ModuleState {
+        self.module_type.state()
+    }
+
     pub fn send_pulse(&mut self, pulse: Pulse, from: &ModuleName) -> Option<SendPulseRequest> {
         self.module_type
             .handle_pulse(pulse, from)
@@ -67,7 +117,19 @@ impl<T: ModuleType> Module<T> {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// What a module remembers, which decides how it handles the next pulses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ModuleState {
+    /// The module handles every pulse the same way.
+    Stateless,
+    FlipFlop {
+        on: bool,
+    },
+    /// The last pulse received from each input, in the order of the input names.
+    Conjunction(Vec<Pulse>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Pulse {
     Low,
     High,
@@ -75,10 +137,7 @@ pub enum Pulse {
 
 impl Pulse {
     fn is_high(&self) -> bool {
-        match self {
-            Self::High => true,
-            _ => false,
-        }
+        matches!(self, Self::High)
     }
 }
 
@@ -92,6 +151,10 @@ mod tests {
     }
 
     impl ModuleType for FakeModType {
+        fn kind(&self) -> ModuleKind {
+            ModuleKind::Output
+        }
+
         fn handle_pulse(&mut self, _pulse: Pulse, _from: &ModuleName) -> Option<Pulse> {
             self.fake_result
         }
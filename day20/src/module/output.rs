@@ -1,6 +1,6 @@
-use super::{ModuleName, ModuleType, Pulse};
+use super::{ModuleKind, ModuleName, ModuleType, Pulse};
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Output;
 
 impl Output {
@@ -10,6 +10,10 @@ impl Output {
 }
 
 impl ModuleType for Output {
+    fn kind(&self) -> ModuleKind {
+        ModuleKind::Output
+    }
+
     fn handle_pulse(&mut self, _pulse: Pulse, _from: &ModuleName) -> Option<Pulse> {
         None
     }
@@ -0,0 +1,168 @@
+//! Hooks into the delivery of pulses, to count, record or watch them while the button is pressed.
+
+use std::{collections::HashMap, ops::ControlFlow};
+
+use crate::module::{ModuleName, Pulse};
+
+/// Something told about every pulse delivered by a [`ModuleSystem`](crate::ModuleSystem).
+///
+/// Observers are either registered with
+/// [`ModuleSystem::add_observer`](crate::ModuleSystem::add_observer) or lent for a single press
+/// with [`ModuleSystem::press_with`](crate::ModuleSystem::press_with). Closures taking the same
+/// arguments as [`observe`](Self::observe) are observers.
+pub trait PulseObserver {
+    /// Called when `pulse` goes from `origin` to `destination`, during the button press number
+    /// `press`, counted from 1.
+    ///
+    /// Returning [`ControlFlow::Break`] stops the press once every observer has seen the pulse
+    /// and it has been delivered: the pulses it would have caused are dropped.
+    fn observe(
+        &mut self,
+        press: u64,
+        origin: &ModuleName,
+        destination: &ModuleName,
+        pulse: Pulse,
+    ) -> ControlFlow<()>;
+}
+
+impl<F> PulseObserver for F
+where
+    F: FnMut(u64, &ModuleName, &ModuleName, Pulse) -> ControlFlow<()>,
+{
+    fn observe(
+        &mut self,
+        press: u64,
+        origin: &ModuleName,
+        destination: &ModuleName,
+        pulse: Pulse,
+    ) -> ControlFlow<()> {
+        self(press, origin, destination, pulse)
+    }
+}
+
+/// Remembers the first press in which each of the `watched` modules sent a high pulse to
+/// `destination`. It never stops a press: keep pressing until [`is_complete`](Self::is_complete).
+#[derive(Debug, Clone)]
+pub struct FirstHighPulses {
+    destination: ModuleName,
+    watched: Vec<ModuleName>,
+    first_presses: HashMap<ModuleName, u64>,
+}
+
+impl FirstHighPulses {
+    pub fn new(watched: Vec<ModuleName>, destination: ModuleName) -> Self {
+        Self {
+            destination,
+            watched,
+            first_presses: HashMap::new(),
+        }
+    }
+
+    /// The first press in which each watched module sent a high pulse, for those which did.
+    pub fn first_presses(&self) -> &HashMap<ModuleName, u64> {
+        &self.first_presses
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.first_presses.len() == self.watched.len()
+    }
+}
+
+impl PulseObserver for FirstHighPulses {
+    fn observe(
+        &mut self,
+        press: u64,
+        origin: &ModuleName,
+        destination: &ModuleName,
+        pulse: Pulse,
+    ) -> ControlFlow<()> {
+        if pulse == Pulse::High && *destination == self.destination && self.watched.contains(origin)
+        {
+            self.first_presses.entry(origin.clone()).or_insert(press);
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{parse, samples::SAMPLE_2};
+
+    use super::*;
+
+    #[test]
+    fn test_registered_observer() {
+        let mut system = parse(SAMPLE_2).unwrap();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+
+        let log = Rc::clone(&seen);
+        system.add_observer(
+            move |press, origin: &ModuleName, destination: &ModuleName, pulse| {
+                log.borrow_mut()
+                    .push(format!("{press} {origin} -{pulse:?}-> {destination}"));
+
+                ControlFlow::Continue(())
+            },
+        );
+
+        let stats = system.run().unwrap();
+        assert_eq!(4, stats.low_pulse_count);
+        assert_eq!(4, stats.high_pulse_count);
+
+        assert_eq!(
+            vec![
+                "1 button -Low-> broadcaster",
+                "1 broadcaster -Low-> a",
+                "1 a -High-> inv",
+                "1 a -High-> con",
+                "1 inv -Low-> b",
+                "1 con -High-> output",
+                "1 b -High-> con",
+                "1 con -Low-> output",
+            ],
+            *seen.borrow()
+        );
+    }
+
+    #[test]
+    fn test_stop_early() {
+        let mut system = parse(SAMPLE_2).unwrap();
+
+        // Stop once `a` sent its first pulse, to `inv`: `con` never gets it, and `inv` does not react.
+        let mut stop_after_a = |_press, origin: &ModuleName, _destination: &ModuleName, _pulse| {
+            if origin == "a" {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+
+        assert!(system.press_with(&mut stop_after_a).unwrap().is_break());
+
+        let stats = system.run().unwrap();
+        assert_eq!(2 + 4, stats.low_pulse_count, "the first press stopped");
+        assert_eq!(1 + 2, stats.high_pulse_count);
+    }
+
+    #[test]
+    fn test_first_high_pulses() {
+        let mut system = parse(SAMPLE_2).unwrap();
+        let mut watcher = FirstHighPulses::new(
+            vec![ModuleName::from("a"), ModuleName::from("b")],
+            ModuleName::from("con"),
+        );
+
+        while !watcher.is_complete() {
+            assert!(system.press_with(&mut watcher).unwrap().is_continue());
+        }
+
+        assert!(watcher.is_complete());
+        assert_eq!(
+            HashMap::from([(ModuleName::from("a"), 1), (ModuleName::from("b"), 1)]),
+            *watcher.first_presses()
+        );
+    }
+}
@@ -1,69 +1,221 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
 
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{alphanumeric1, multispace0, multispace1},
-    multi::separated_list0,
-    sequence::{delimited, preceded, separated_pair, terminated},
+    combinator::eof,
+    sequence::{delimited, preceded, terminated},
     IResult,
 };
 
 use crate::{
     module::{Broadcaster, Conjunction, FlipFlop, Module, ModuleName, ModuleType},
-    ModuleRegistry, ModuleSystem,
+    ModuleNotFoundError, ModuleRegistry, ModuleSystem,
 };
 
-pub fn parse(input: &str) -> ModuleSystem {
-    let definitions: Vec<(Box<dyn ModuleType>, ModuleName, Vec<ModuleName>)> = input
-        .lines()
-        .map(parse_module_definition)
-        .map(Result::unwrap)
-        .map(|(_remain, definition)| definition)
-        .collect();
+/// Why the module configuration could not be read. Lines and columns start at 1, and columns
+/// count characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// Something else than the described token was found.
+    Expected(&'static str),
+    /// The module was already defined on `first_line`.
+    Duplicate { name: ModuleName, first_line: usize },
+    /// The destination is neither defined nor a declared sink, in [`parse_strict`].
+    Undefined(ModuleNotFoundError),
+}
+
+impl std::error::Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+
+        match &self.kind {
+            ParseErrorKind::Expected(token) => write!(f, "expected {token}"),
+            ParseErrorKind::Duplicate { name, first_line } => {
+                write!(f, "module {name} is already defined on line {first_line}")
+            }
+            ParseErrorKind::Undefined(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+const MODULE: &str = "`broadcaster`, `%name` or `&name`";
+const ARROW: &str = "` -> `";
+const MODULE_NAME: &str = "a module name";
+const SEPARATOR: &str = "`,` or the end of the line";
+
+/// A line and a column.
+type Location = (usize, usize);
+
+/// A module as defined on a line of the input, with the columns of its destinations.
+struct ModuleDefinition {
+    mod_type: Box<dyn ModuleType>,
+    name: ModuleName,
+    line: usize,
+    destinations: Vec<(ModuleName, usize)>,
+}
+
+/// Parse the module configuration, one module per line. Empty lines are ignored.
+///
+/// The destinations which are not defined, such as `output` or `rx`, receive pulses without
+/// reacting.
+pub fn parse(input: &str) -> Result<ModuleSystem, ParseError> {
+    let (mut system, undefined) = build(input)?;
+    system.sinks = undefined.into_iter().map(|(name, _at)| name).collect();
+
+    Ok(system)
+}
+
+/// Parse the module configuration, where the only destinations which may not be defined are the
+/// declared `sinks`.
+///
+/// The problems which do not prevent the system from working, such as unreachable modules, are
+/// logged as warnings.
+pub fn parse_strict(input: &str, sinks: &[&str]) -> Result<ModuleSystem, ParseError> {
+    let (mut system, undefined) = build(input)?;
+
+    if let Some((name, (line, column))) = undefined
+        .into_iter()
+        .find(|(name, _at)| !sinks.contains(&name.as_str()))
+    {
+        return Err(ParseError {
+            line,
+            column,
+            kind: ParseErrorKind::Undefined(ModuleNotFoundError(name)),
+        });
+    }
+
+    system.sinks = sinks.iter().map(|sink| ModuleName::from(*sink)).collect();
+
+    for warning in system.validate() {
+        log::warn!("{warning}");
+    }
+
+    Ok(system)
+}
+
+/// Build the system defined by `input`, without sinks, and list the destinations which are not
+/// defined with the line and column where they first appear.
+fn build(input: &str) -> Result<(ModuleSystem, Vec<(ModuleName, Location)>), ParseError> {
+    let mut definitions: Vec<ModuleDefinition> = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let definition = parse_module_definition(index + 1, line)?;
+
+        if let Some(first) = definitions
+            .iter()
+            .find(|first| first.name == definition.name)
+        {
+            return Err(ParseError {
+                line: definition.line,
+                column: 1,
+                kind: ParseErrorKind::Duplicate {
+                    name: definition.name,
+                    first_line: first.line,
+                },
+            });
+        }
+
+        definitions.push(definition);
+    }
 
     let mut inputs: HashMap<ModuleName, Vec<ModuleName>> = HashMap::new();
+    let mut undefined: Vec<(ModuleName, Location)> = Vec::new();
 
-    for (_mod_type, name, destinations) in definitions.iter() {
-        for destination in destinations {
+    for definition in definitions.iter() {
+        for (destination, column) in &definition.destinations {
             inputs
                 .entry(destination.clone())
-                .or_insert_with(Vec::new)
-                .push(name.clone());
+                .or_default()
+                .push(definition.name.clone());
+
+            let defined = definitions.iter().any(|other| other.name == *destination);
+
+            if !defined && !undefined.iter().any(|(name, _at)| name == destination) {
+                undefined.push((destination.clone(), (definition.line, *column)));
+            }
         }
     }
 
     let modules: ModuleRegistry = definitions
         .into_iter()
-        .map(|(mod_type, name, destinations)| {
+        .map(|definition| {
+            let name = definition.name;
+            let destinations = definition
+                .destinations
+                .into_iter()
+                .map(|(destination, _column)| destination)
+                .collect();
+
             let mod_inputs = inputs.remove(&name).unwrap_or(vec![]);
-            let module = Module::new(name.clone(), mod_type, mod_inputs, destinations);
+            let module = Module::new(name.clone(), definition.mod_type, mod_inputs, destinations);
 
             (name, module)
         })
         .collect();
 
-    ModuleSystem::from(modules)
+    Ok((ModuleSystem::from(modules), undefined))
 }
 
-fn parse_module_definition(
-    input: &str,
-) -> IResult<&str, (Box<dyn ModuleType>, ModuleName, Vec<ModuleName>)> {
-    let (remain, ((module_type, name), destinations)) = separated_pair(
-        parse_module,
-        delimited(multispace1, tag("->"), multispace1),
-        parse_destinations,
-    )(input)?;
+/// Parse the definition on the line number `line`, which may only be followed by whitespace.
+fn parse_module_definition(line: usize, input: &str) -> Result<ModuleDefinition, ParseError> {
+    let column = |remain: &str| input[..input.len() - remain.len()].chars().count() + 1;
+    let expected = |remain: &str, token| ParseError {
+        line,
+        column: column(remain),
+        kind: ParseErrorKind::Expected(token),
+    };
+
+    let (remain, (mod_type, name)) = parse_module(input).map_err(|_| expected(input, MODULE))?;
+    let (mut remain, _arrow) = parse_arrow(remain).map_err(|_| expected(remain, ARROW))?;
+
+    let mut destinations = Vec::new();
+
+    loop {
+        let (rest, destination) =
+            parse_module_name(remain).map_err(|_| expected(remain, MODULE_NAME))?;
+        destinations.push((destination, column(remain)));
+
+        if rest.trim_end().is_empty() {
+            break;
+        }
+
+        (remain, _) = parse_separator(rest).map_err(|_| expected(rest, SEPARATOR))?;
+    }
 
-    Ok((remain, (module_type, name, destinations)))
+    Ok(ModuleDefinition {
+        mod_type,
+        name,
+        line,
+        destinations,
+    })
 }
 
 fn parse_module(input: &str) -> IResult<&str, (Box<dyn ModuleType>, ModuleName)> {
     alt((parse_broadcaster, parse_flip_flop, parse_conjunction))(input)
 }
 
-fn parse_destinations(input: &str) -> IResult<&str, Vec<ModuleName>> {
-    separated_list0(terminated(tag(","), multispace0), parse_module_name)(input)
+fn parse_arrow(input: &str) -> IResult<&str, &str> {
+    // At the end of the line, the missing destination is the problem, not the arrow.
+    delimited(multispace1, tag("->"), alt((multispace1, eof)))(input)
+}
+
+fn parse_separator(input: &str) -> IResult<&str, &str> {
+    terminated(tag(","), multispace0)(input)
 }
 
 fn parse_module_name(input: &str) -> IResult<&str, ModuleName> {
@@ -94,3 +246,109 @@ fn parse_conjunction(input: &str) -> IResult<&str, (Box<dyn ModuleType>, ModuleN
         (remain, (mod_type, name.clone()))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::samples::SAMPLE_2;
+
+    use super::*;
+
+    fn error_at(input: &str) -> (usize, usize, ParseErrorKind) {
+        let error = parse(input).err().unwrap();
+
+        (error.line, error.column, error.kind)
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(SAMPLE_2).is_ok());
+        assert!(parse("\nbroadcaster -> a  \n\n%a -> b\n").is_ok());
+
+        assert_eq!(
+            (2, 1, ParseErrorKind::Expected(MODULE)),
+            error_at("broadcaster -> a\n*a -> b\n")
+        );
+        assert_eq!(
+            (1, 3, ParseErrorKind::Expected(ARROW)),
+            error_at("%a => b\n")
+        );
+        assert_eq!(
+            (1, 9, ParseErrorKind::Expected(MODULE_NAME)),
+            error_at("%a -> b,\n")
+        );
+        assert_eq!(
+            (1, 16, ParseErrorKind::Expected(MODULE_NAME)),
+            error_at("broadcaster -> \n")
+        );
+        assert_eq!(
+            (1, 15, ParseErrorKind::Expected(MODULE_NAME)),
+            error_at("broadcaster ->\n")
+        );
+        assert_eq!(
+            (1, 11, ParseErrorKind::Expected(MODULE_NAME)),
+            error_at("%a -> b,  \n")
+        );
+        assert_eq!(
+            (1, 8, ParseErrorKind::Expected(SEPARATOR)),
+            error_at("%a -> b; c\n")
+        );
+        assert_eq!(
+            (1, 8, ParseErrorKind::Expected(SEPARATOR)),
+            error_at("%a -> bé\n"),
+            "columns count characters"
+        );
+        assert_eq!(
+            (
+                3,
+                1,
+                ParseErrorKind::Duplicate {
+                    name: ModuleName::from("a"),
+                    first_line: 1,
+                }
+            ),
+            error_at("%a -> b\n%b -> a\n&a -> b\n")
+        );
+
+        assert_eq!(
+            "line 1, column 8: expected `,` or the end of the line",
+            parse("%a -> b c").err().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let error = parse_strict(SAMPLE_2, &[]).err().unwrap();
+        assert_eq!(
+            ParseError {
+                line: 5,
+                column: 9,
+                kind: ParseErrorKind::Undefined(ModuleNotFoundError(ModuleName::from("output"))),
+            },
+            error
+        );
+        assert_eq!(
+            "line 5, column 9: module not found: output",
+            error.to_string()
+        );
+
+        let mut system = parse_strict(SAMPLE_2, &["output"]).unwrap();
+        assert!(system.run().is_ok());
+
+        let error = parse_strict("broadcaster -> a, b\n%a -> c\n", &["c"])
+            .err()
+            .unwrap();
+        assert_eq!((1, 19), (error.line, error.column));
+    }
+
+    #[test]
+    fn test_dangling_destination() {
+        let mut system = parse(SAMPLE_2).unwrap();
+        assert!(system.run().is_ok(), "undefined destinations are sinks");
+
+        let mut system = parse_strict(SAMPLE_2, &["output"]).unwrap();
+        system.sinks.clear();
+
+        let error = system.run().err().unwrap();
+        assert_eq!("module not found: output", error.to_string());
+    }
+}
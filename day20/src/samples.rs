@@ -0,0 +1,30 @@
+//! The example configurations of the puzzle, shared by the tests.
+
+/// The first example: a chain of flip-flops looped back through an inverter.
+pub const SAMPLE_1: &str = "\
+broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a
+";
+
+/// The second example, which sends pulses to an `output` module without definition.
+pub const SAMPLE_2: &str = "\
+broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output
+";
+
+/// Two counters feeding the conjunction `hub`, which first sends `rx` a low pulse on press 4.
+pub const SAMPLE_3: &str = "\
+broadcaster -> a1, b1
+%a1 -> ia
+&ia -> hub
+%b1 -> b2
+%b2 -> ib
+&ib -> hub
+&hub -> rx
+";
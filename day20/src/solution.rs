@@ -0,0 +1,76 @@
+use std::iter;
+
+use aocutil::{cycle, Solution};
+
+use crate::{parse, parse_strict, ModuleSystemStats};
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn part1(input: &str) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> u64 {
+        part2(input)
+    }
+}
+
+const PRESSES: usize = 1000;
+
+fn part1(input: &str) -> usize {
+    let mut system = parse(input).unwrap();
+    let mut totals = vec![ModuleSystemStats::default()];
+
+    let start = system.state();
+    let presses = iter::repeat_with(|| {
+        totals.push(system.run().unwrap());
+        system.state()
+    });
+    let history = cycle::record_iter(iter::once(start).chain(presses), PRESSES);
+
+    let (low, high) = (0..PRESSES)
+        .map(|press| history.equivalent_step(press).unwrap())
+        .map(|step| (totals[step], totals[step + 1]))
+        .fold((0, 0), |(low, high), (before, after)| {
+            (
+                low + after.low_pulse_count - before.low_pulse_count,
+                high + after.high_pulse_count - before.high_pulse_count,
+            )
+        });
+
+    low * high
+}
+
+fn part2(input: &str) -> u64 {
+    let mut system = parse_strict(input, &["rx"]).unwrap();
+
+    system.presses_until_low_pulse("rx").unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::samples::{SAMPLE_1, SAMPLE_2, SAMPLE_3};
+
+    use super::*;
+
+    #[test]
+    fn part1_with_sample1() {
+        assert_eq!(32000000, part1(SAMPLE_1));
+    }
+
+    #[test]
+    fn part1_with_sample2() {
+        assert_eq!(11687500, part1(SAMPLE_2));
+    }
+
+    #[test]
+    fn part2_with_sample3() {
+        assert_eq!(4, part2(SAMPLE_3));
+    }
+}
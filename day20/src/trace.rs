@@ -0,0 +1,230 @@
+//! Recording of every pulse delivered by a [`ModuleSystem`](crate::ModuleSystem), one line per
+//! pulse, in the format of the puzzle examples prefixed by the press number and the index of the
+//! pulse in the press:
+//!
+//! ```text
+//! 1 0 button -low-> broadcaster
+//! 1 1 broadcaster -low-> a
+//! ```
+
+use std::{fmt::Display, ops::ControlFlow, str::FromStr};
+
+use crate::{
+    module::{ModuleName, Pulse},
+    observer::PulseObserver,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// The button press which led to the pulse, from 1.
+    pub press: u64,
+    /// The position of the pulse among the pulses of its press, from 0.
+    pub index: usize,
+    pub origin: ModuleName,
+    pub destination: ModuleName,
+    pub pulse: Pulse,
+}
+
+/// The pulses delivered while recording, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    entries: Vec<TraceEntry>,
+}
+
+impl Trace {
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    /// The first pulse matching `predicate`.
+    pub fn find(&self, predicate: impl FnMut(&&TraceEntry) -> bool) -> Option<&TraceEntry> {
+        self.entries.iter().find(predicate)
+    }
+
+    /// The first press in which `origin` sent `pulse` to `destination`.
+    pub fn first_press(&self, origin: &str, pulse: Pulse, destination: &str) -> Option<u64> {
+        self.find(|entry| {
+            entry.origin == origin && entry.pulse == pulse && entry.destination == destination
+        })
+        .map(|entry| entry.press)
+    }
+
+    /// The pulses of `press`.
+    pub fn press(&self, press: u64) -> impl Iterator<Item = &TraceEntry> + '_ {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.press == press)
+    }
+}
+
+impl PulseObserver for Trace {
+    fn observe(
+        &mut self,
+        press: u64,
+        origin: &ModuleName,
+        destination: &ModuleName,
+        pulse: Pulse,
+    ) -> ControlFlow<()> {
+        let index = self
+            .entries
+            .last()
+            .filter(|last| last.press == press)
+            .map_or(0, |last| last.index + 1);
+
+        self.entries.push(TraceEntry {
+            press,
+            index,
+            origin: origin.clone(),
+            destination: destination.clone(),
+            pulse,
+        });
+
+        ControlFlow::Continue(())
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{entry}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pulse = match self.pulse {
+            Pulse::Low => "low",
+            Pulse::High => "high",
+        };
+
+        write!(
+            f,
+            "{} {} {} -{pulse}-> {}",
+            self.press, self.index, self.origin, self.destination
+        )
+    }
+}
+
+impl FromStr for Trace {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = s
+            .lines()
+            .enumerate()
+            .filter(|(_index, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                line.parse()
+                    .map_err(|err| format!("line {}: {err}", index + 1))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { entries })
+    }
+}
+
+impl FromStr for TraceEntry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+
+        let [press, index, origin, arrow, destination] = fields[..] else {
+            return Err(String::from(
+                "expected `<press> <index> <origin> -<pulse>-> <destination>`",
+            ));
+        };
+
+        let pulse = match arrow {
+            "-low->" => Pulse::Low,
+            "-high->" => Pulse::High,
+            arrow => return Err(format!("expected `-low->` or `-high->`, found `{arrow}`")),
+        };
+
+        Ok(Self {
+            press: press
+                .parse()
+                .map_err(|_| format!("invalid press number `{press}`"))?,
+            index: index
+                .parse()
+                .map_err(|_| format!("invalid pulse index `{index}`"))?,
+            origin: ModuleName::from(origin),
+            destination: ModuleName::from(destination),
+            pulse,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, samples::SAMPLE_1};
+
+    use super::*;
+
+    #[test]
+    fn test_record_trace() {
+        let mut system = parse(SAMPLE_1).unwrap();
+
+        system.run().unwrap();
+        system.record_trace();
+        system.run().unwrap();
+        system.run().unwrap();
+
+        let trace = system.take_trace().unwrap();
+        assert_eq!(None, system.trace(), "recording stopped");
+
+        assert_eq!(24, trace.entries().len());
+        assert_eq!(
+            "\
+2 0 button -low-> broadcaster
+2 1 broadcaster -low-> a
+2 2 broadcaster -low-> b
+2 3 broadcaster -low-> c
+2 4 a -high-> b
+2 5 b -high-> c
+2 6 c -high-> inv
+2 7 inv -low-> a
+2 8 a -low-> b
+2 9 b -low-> c
+2 10 c -low-> inv
+2 11 inv -high-> a
+",
+            trace
+                .press(2)
+                .map(|entry| format!("{entry}\n"))
+                .collect::<String>()
+        );
+
+        assert_eq!(Some(2), trace.first_press("inv", Pulse::High, "a"));
+        assert_eq!(None, trace.first_press("a", Pulse::High, "inv"));
+        assert_eq!(
+            Some(3),
+            trace
+                .find(|entry| entry.press == 3 && entry.index == 11)
+                .map(|entry| entry.press)
+        );
+    }
+
+    #[test]
+    fn test_trace_round_trip() {
+        let mut system = parse(SAMPLE_1).unwrap();
+
+        system.record_trace();
+        system.run().unwrap();
+
+        let trace = system.trace().unwrap();
+        assert_eq!(Ok(trace), trace.to_string().parse().as_ref());
+
+        assert_eq!(
+            Err(String::from(
+                "line 2: expected `-low->` or `-high->`, found `->`"
+            )),
+            "1 0 button -low-> broadcaster\n1 1 broadcaster -> a\n".parse::<Trace>()
+        );
+        assert!("x 0 a -low-> b".parse::<Trace>().is_err());
+        assert!("1 0 a -low->".parse::<Trace>().is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Display,
+};
+
+use crate::{
+    module::{ModuleKind, ModuleName},
+    ModuleSystem,
+};
+
+/// Something suspicious about the way the modules are connected, which does not prevent pressing
+/// the button.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationWarning {
+    /// There is no `broadcaster`, so pressing the button does nothing.
+    MissingBroadcaster,
+    /// No pulse from the button ever reaches the module.
+    Unreachable(ModuleName),
+    /// The conjunction remembers nothing, so it only ever sends low pulses.
+    ConjunctionWithoutInputs(ModuleName),
+}
+
+impl Display for ValidationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationWarning::MissingBroadcaster => write!(f, "no broadcaster module"),
+            ValidationWarning::Unreachable(name) => write!(f, "module {name} is unreachable"),
+            ValidationWarning::ConjunctionWithoutInputs(name) => {
+                write!(f, "conjunction {name} has no inputs")
+            }
+        }
+    }
+}
+
+impl ModuleSystem {
+    /// Look for suspicious connections, listing the modules in the order of their names.
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let mut names: Vec<&ModuleName> = self.modules.keys().collect();
+        names.sort();
+
+        let mut warnings = Vec::new();
+
+        if self.modules.contains_key("broadcaster") {
+            let reachable = self.reachable_from("broadcaster");
+
+            warnings.extend(
+                names
+                    .iter()
+                    .filter(|name| !reachable.contains(*name))
+                    .map(|name| ValidationWarning::Unreachable((*name).clone())),
+            );
+        } else {
+            warnings.push(ValidationWarning::MissingBroadcaster);
+        }
+
+        warnings.extend(
+            names
+                .iter()
+                .filter(|name| {
+                    let module = &self.modules[**name];
+
+                    module.kind() == ModuleKind::Conjunction && module.inputs().is_empty()
+                })
+                .map(|name| ValidationWarning::ConjunctionWithoutInputs((*name).clone())),
+        );
+
+        warnings
+    }
+
+    /// The modules which pulses sent by `start` can reach, including `start`.
+    fn reachable_from(&self, start: &str) -> HashSet<&ModuleName> {
+        let mut reached: HashSet<&ModuleName> = HashSet::new();
+        let mut pending: VecDeque<&ModuleName> = self
+            .modules
+            .get_key_value(start)
+            .map(|(name, _module)| name)
+            .into_iter()
+            .collect();
+
+        while let Some(name) = pending.pop_front() {
+            if !reached.insert(name) {
+                continue;
+            }
+
+            let outputs = self.modules[name].outputs();
+            pending.extend(
+                outputs
+                    .iter()
+                    .filter_map(|output| self.modules.get_key_value(output))
+                    .map(|(name, _module)| name),
+            );
+        }
+
+        reached
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let system = parse(
+            "\
+broadcaster -> a
+%a -> con
+&con -> output
+%lost -> a
+&empty -> lost
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                ValidationWarning::Unreachable(ModuleName::from("empty")),
+                ValidationWarning::Unreachable(ModuleName::from("lost")),
+                ValidationWarning::ConjunctionWithoutInputs(ModuleName::from("empty")),
+            ],
+            system.validate()
+        );
+
+        let system = parse("%a -> b\n%b -> a\n").unwrap();
+        assert_eq!(
+            vec![ValidationWarning::MissingBroadcaster],
+            system.validate()
+        );
+        assert_eq!("no broadcaster module", system.validate()[0].to_string());
+
+        let system = parse("broadcaster -> a\n%a -> broadcaster\n").unwrap();
+        assert_eq!(Vec::<ValidationWarning>::new(), system.validate());
+    }
+}
@@ -0,0 +1,118 @@
+use std::{collections::HashMap, str::FromStr};
+
+use aocutil::Solution;
+use chargrid::{CharGrid, Pos};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn part1(input: &str) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> u64 {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> u64 {
+    let grid = CharGrid::from_str(input).unwrap();
+
+    grid.runs(|c| is_digit(&c))
+        .into_iter()
+        .filter(|number| {
+            grid.span_neighbours(number)
+                .into_iter()
+                .any(|(_pos, val)| val != '.' && !is_digit(&val))
+        })
+        .map(|number| parse_u64(&number.text))
+        .sum()
+}
+
+fn part2(input: &str) -> u64 {
+    let grid = CharGrid::from_str(input).unwrap();
+
+    let mut gears_parts: HashMap<Pos, Vec<u64>> = HashMap::new();
+
+    for number in grid.runs(|c| is_digit(&c)) {
+        let part_number = parse_u64(&number.text);
+
+        for (gear_pos, _val) in grid
+            .span_neighbours(&number)
+            .into_iter()
+            .filter(|(_pos, val)| *val == '*')
+        {
+            gears_parts.entry(gear_pos).or_default().push(part_number);
+        }
+    }
+
+    gears_parts
+        .values()
+        .filter(|part_numbers| part_numbers.len() >= 2)
+        .map(|part_numbers| part_numbers.iter().product::<u64>())
+        .sum()
+}
+
+fn is_digit(c: &char) -> bool {
+    c.is_ascii_digit()
+}
+
+fn parse_u64(s: &str) -> u64 {
+    s.parse().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    const SAMPLE_2: &str = "\
+467..114..
+...*......
+..35...633
+1.....#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    #[test]
+    fn part1_with_sample() {
+        assert_eq!(4361, part1(SAMPLE));
+    }
+
+    #[test]
+    fn part1_with_sample2() {
+        assert_eq!(4361, part1(SAMPLE_2));
+    }
+
+    #[test]
+    fn part2_with_sample() {
+        assert_eq!(467835, part2(SAMPLE));
+    }
+
+    #[test]
+    fn part2_with_sample2() {
+        assert_eq!(467835, part2(SAMPLE_2));
+    }
+}